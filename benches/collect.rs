@@ -0,0 +1,63 @@
+use std::hint::black_box;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use rsysfetch::{Config, MockCollector, SystemCollector, SystemInfo};
+
+fn collect(c: &mut Criterion) {
+    let mut group = c.benchmark_group("collect");
+    group.sample_size(20);
+
+    // Only CPU and memory are refreshed up front; the process table isn't
+    group.bench_function("system collector", |b| b.iter(SystemCollector::new));
+
+    // No commands, so the difference is the process enumeration
+    for (name, count_processes) in [("without processes", false), ("with processes", true)] {
+        let config = Config {
+            no_exec: true,
+            count_processes,
+            ..Config::default()
+        };
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                SystemInfo::collect_from(black_box(&config), Arc::new(MockCollector::default()))
+            })
+        });
+    }
+
+    let default = Config::default();
+    let fast = Config {
+        fast: true,
+        ..Config::default()
+    };
+    group.bench_function("default", |b| {
+        b.iter(|| SystemInfo::collect(black_box(&default)))
+    });
+    group.bench_function("fast", |b| b.iter(|| SystemInfo::collect(black_box(&fast))));
+
+    group.finish();
+
+    // Criterion reports each function on its own, so put the two presets
+    // side by side
+    let default_time = mean_time(|| SystemInfo::collect(&default));
+    let fast_time = mean_time(|| SystemInfo::collect(&fast));
+    println!(
+        "collect/default {default_time:?} vs collect/fast {fast_time:?}: fast is {:.0}x faster",
+        default_time.as_secs_f64() / fast_time.as_secs_f64()
+    );
+}
+
+/// Mean duration of a few runs, after a warm-up run
+fn mean_time<T>(mut run: impl FnMut() -> T) -> Duration {
+    const RUNS: u32 = 10;
+    black_box(run());
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(run());
+    }
+    start.elapsed() / RUNS
+}
+
+criterion_group!(benches, collect);
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+fn main() {
+    // Exposed to the crate for `--version`
+    println!(
+        "cargo:rustc-env=TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+}
@@ -1,19 +1,275 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ratatui::text::Line;
+use rsysfetch::SystemCollector;
+use sysinfo::{Networks, System};
+
 use crate::AppResult;
+use crate::config::Config;
+use crate::demo::Demo;
+use crate::logo;
+use crate::print;
+use crate::snapshot;
 use crate::system_info::SystemInfo;
+use crate::theme::Palette;
+
+/// How long fields that changed since the previous launch stay marked
+const CHANGE_HIGHLIGHT: Duration = Duration::from_secs(5);
+
+/// How often a running background collection is checked for completion
+const REFRESH_POLL: Duration = Duration::from_millis(100);
+
+/// How long a message replaces the footer
+const STATUS_DURATION: Duration = Duration::from_secs(3);
+
+/// Line width of the text copied with 'y'
+const COPY_WIDTH: u16 = 100;
 
 #[derive(Debug)]
 pub struct App {
     pub system_info: SystemInfo,
+    pub config: Config,
+    pub palette: Palette,
+    /// Art from the `logo` option, replacing the built-in logo
+    pub custom_logo: Option<Vec<Line<'static>>>,
+    /// Network addresses are masked while true; toggled with 'i'
+    pub hide_ip: bool,
+    /// Per-core usage panel is open; toggled with 'c'
+    pub show_cores: bool,
+    /// Network interfaces panel is open; toggled with 'n'
+    pub show_interfaces: bool,
+    /// First visible row of the info text; moved with the arrow and page
+    /// keys or the mouse wheel
+    pub scroll: u16,
+    /// Recent CPU usage samples per CPU model, newest last (watch mode)
+    pub cpu_history: HashMap<String, VecDeque<f32>>,
+    /// Maximum samples kept per history, follows the panel width
+    pub history_cap: usize,
+    /// Kept between ticks so CPU usage is measured over the interval
+    sys: System,
+    /// Network counters and when they were last read, for transfer rates
+    /// in watch and refresh modes
+    networks: Option<(Networks, Instant)>,
+    /// Drives the sample data instead of `sys` in demo mode
+    demo: Option<Demo>,
+    /// Keys of the sections that changed since the previous launch
+    pub changed: Vec<String>,
+    /// When the change markers disappear
+    changed_until: Instant,
+    /// When the next full collection starts (`refresh` option)
+    next_refresh: Instant,
+    /// Result of the full collection running in the background, if any
+    pending_refresh: Option<Receiver<Result<SystemInfo, String>>>,
+    /// Short-lived message shown instead of the footer, and when it expires
+    status: Option<(String, Instant)>,
+    /// Kept open after copying: on X11 the copied text is served by its
+    /// owner, so it would be gone once the clipboard is dropped
+    clipboard: Option<Clipboard>,
     pub should_quit: bool,
 }
 
 impl App {
-    pub fn new() -> AppResult<Self> {
-        let system_info = SystemInfo::collect()?;
+    pub fn new(config: Config) -> AppResult<Self> {
+        let demo = config.demo_seed.map(Demo::new);
+        let system_info = collect(&config)?;
+
+        let palette = Palette::resolve(&config);
+        let custom_logo = config.logo.as_deref().map(logo::from_file).transpose()?;
+
+        // Made-up or masked values would spoil the saved snapshot
+        let changed = if config.highlight_changes && demo.is_none() && !config.anonymize {
+            snapshot::changed_sections(&system_info)
+        } else {
+            Vec::new()
+        };
+
+        let config_refresh = config.refresh;
+        let mut sys = System::new();
+        if config.watch && demo.is_none() {
+            sys.refresh_cpu();
+        }
+        // The first read only sets the baseline, rates need a second one
+        let networks = ((config.watch || config.refresh > 0) && demo.is_none())
+            .then(|| (Networks::new_with_refreshed_list(), Instant::now()));
 
         Ok(Self {
             system_info,
+            hide_ip: config.hide_ip,
+            show_cores: config.cpu_cores,
+            show_interfaces: false,
+            scroll: 0,
+            palette,
+            custom_logo,
+            config,
+            cpu_history: HashMap::new(),
+            history_cap: 0,
+            sys,
+            networks,
+            demo,
+            changed,
+            changed_until: Instant::now() + CHANGE_HIGHLIGHT,
+            next_refresh: Instant::now() + Duration::from_secs(config_refresh),
+            pending_refresh: None,
+            status: None,
+            clipboard: None,
             should_quit: false,
         })
     }
+
+    /// Time left before the change markers disappear, if any are shown
+    pub fn highlight_remaining(&self) -> Option<Duration> {
+        let remaining = self.changed_until.checked_duration_since(Instant::now())?;
+        (!self.changed.is_empty() && !remaining.is_zero()).then_some(remaining)
+    }
+
+    /// Move the info text by `rows`, keeping it within `max` rows of scroll
+    pub fn scroll_by(&mut self, rows: i32, max: u16) {
+        self.scroll = (self.scroll.min(max) as i32 + rows).clamp(0, max as i32) as u16;
+    }
+
+    /// Message to show in place of the footer, while it lasts
+    pub fn status(&self) -> Option<&str> {
+        self.status
+            .as_ref()
+            .filter(|(_, until)| Instant::now() < *until)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Time left before the footer message disappears, if one is shown
+    pub fn status_remaining(&self) -> Option<Duration> {
+        let (_, until) = self.status.as_ref()?;
+        let remaining = until.checked_duration_since(Instant::now())?;
+        (!remaining.is_zero()).then_some(remaining)
+    }
+
+    /// Copy the info as plain text laid out like `--print`, reporting the
+    /// outcome in the footer; headless systems have no clipboard to copy to
+    pub fn copy_to_clipboard(&mut self) {
+        let text = print::render(self, COPY_WIDTH, false);
+        let result = match &mut self.clipboard {
+            Some(clipboard) => clipboard.0.set_text(text),
+            None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                clipboard.set_text(text)?;
+                self.clipboard = Some(Clipboard(clipboard));
+                Ok(())
+            }),
+        };
+        let message = match result {
+            Ok(()) => "Copied to clipboard".to_string(),
+            Err(err) => format!("Cannot copy: {err}"),
+        };
+        self.status = Some((message, Instant::now() + STATUS_DURATION));
+    }
+
+    /// Time until the event loop should call `update_refresh` again, if a
+    /// periodic refresh is configured
+    pub fn refresh_timeout(&self) -> Option<Duration> {
+        if self.config.refresh == 0 || self.demo.is_some() {
+            None
+        } else if self.pending_refresh.is_some() {
+            Some(REFRESH_POLL)
+        } else {
+            Some(self.next_refresh.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    /// Swap in the result of a finished background collection, or start the
+    /// next one when it is due
+    pub fn update_refresh(&mut self) {
+        if let Some(receiver) = &self.pending_refresh {
+            match receiver.try_recv() {
+                Err(TryRecvError::Empty) => return,
+                Ok(Ok(system_info)) => {
+                    self.system_info = system_info;
+                    self.sample_networks();
+                }
+                // Keep showing the previous values
+                Ok(Err(_)) | Err(TryRecvError::Disconnected) => {}
+            }
+            self.pending_refresh = None;
+            self.next_refresh = Instant::now() + Duration::from_secs(self.config.refresh);
+        } else if self
+            .refresh_timeout()
+            .is_some_and(|timeout| timeout.is_zero())
+        {
+            self.refresh();
+        }
+    }
+
+    /// Collect everything again on a background thread, so input stays
+    /// responsive; `update_refresh` picks up the result
+    pub fn refresh(&mut self) {
+        if self.pending_refresh.is_some() || self.demo.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let config = self.config.clone();
+        thread::spawn(move || {
+            let _ = sender.send(collect(&config).map_err(|err| err.to_string()));
+        });
+        self.pending_refresh = Some(receiver);
+    }
+
+    /// Read the network counters again and update the transfer rates
+    fn sample_networks(&mut self) {
+        if let Some((networks, sampled)) = &mut self.networks {
+            networks.refresh();
+            self.system_info
+                .refresh_network_rates(networks, sampled.elapsed());
+            *sampled = Instant::now();
+        }
+    }
+
+    /// Refresh CPU and memory figures and record the CPU usage history
+    pub fn tick(&mut self) {
+        if let Some(demo) = &mut self.demo {
+            demo.step(&mut self.system_info);
+        } else {
+            self.sys.refresh_cpu();
+            self.sys.refresh_memory();
+            self.system_info.refresh_dynamic(&self.sys);
+            self.sample_networks();
+        }
+
+        for cpu in &self.system_info.cpus {
+            let history = self.cpu_history.entry(cpu.model.clone()).or_default();
+            history.push_back(cpu.usage);
+            while history.len() > self.history_cap {
+                history.pop_front();
+            }
+        }
+    }
+}
+
+/// `arboard::Clipboard` without a `Debug` impl of its own
+struct Clipboard(arboard::Clipboard);
+
+impl fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clipboard")
+    }
+}
+
+/// Collect the values to show, honouring demo mode and anonymization
+pub fn collect(config: &Config) -> AppResult<SystemInfo> {
+    let mut system_info = if config.demo_seed.is_some() {
+        SystemInfo::sample()
+    } else if config.fast || config.timeout == 0 {
+        SystemInfo::collect(config)?
+    } else {
+        SystemInfo::collect_with_timeout(
+            config,
+            Arc::new(SystemCollector::new()),
+            Duration::from_secs(config.timeout),
+        )?
+    };
+    if config.anonymize {
+        system_info.anonymize();
+    }
+    Ok(system_info)
 }
@@ -0,0 +1,297 @@
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+use crate::config::{Config, IconMode, IpVersion, TemperatureUnit, ThemePreset};
+use crate::demo;
+
+/// `--version` output: the crate version and the target it was built for
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (", env!("TARGET"), ")");
+
+/// What the program should do after parsing arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Launch the interactive TUI
+    Tui,
+    /// Print field metadata as JSON and exit
+    FieldsJson,
+    /// Print the collected values as JSON and exit
+    Json,
+    /// Print the collected values as YAML and exit
+    Yaml,
+    /// Print the logo and a Markdown table of the info lines and exit
+    Markdown,
+    /// Print plain "label: value" lines and exit
+    Kv,
+    /// Print one plain "label: value" line per section and exit
+    Compact,
+    /// Print the logo beside the info once and exit
+    Print,
+    /// Write the rendered TUI to an SVG file and exit
+    Svg(PathBuf),
+}
+
+/// Values of `--format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Tui,
+    Json,
+    Yaml,
+    #[value(alias = "md")]
+    Markdown,
+    #[value(alias = "text")]
+    Kv,
+}
+
+/// Command line definition; `--help` and `--version` come with it
+#[derive(Debug, Parser)]
+#[command(name = "rsysfetch", version = VERSION, about)]
+pub struct Cli {
+    /// Print the info in this format and exit
+    #[arg(long, value_name = "FORMAT")]
+    pub format: Option<Format>,
+    /// Print the logo beside the info once and exit
+    #[arg(short, long)]
+    pub print: bool,
+    /// Print one plain line per field and exit
+    #[arg(long)]
+    pub compact: bool,
+    /// Write the output to a file instead of stdout
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+    /// Render one frame into an SVG image and exit
+    #[arg(long, value_name = "PATH")]
+    pub svg: Option<PathBuf>,
+    /// Print metadata about the collected fields as JSON
+    #[arg(long)]
+    pub fields_json: bool,
+    /// Apply config.<NAME>.toml over config.toml
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Comma-separated section keys to show, in order
+    #[arg(long, value_name = "KEYS")]
+    pub fields: Option<String>,
+    /// Color preset
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<ThemePreset>,
+    /// Icon set
+    #[arg(long, value_name = "SET")]
+    pub icons: Option<IconMode>,
+    /// Address family of the local IP
+    #[arg(long, value_name = "VERSION")]
+    pub ip_version: Option<IpVersion>,
+    /// Collect everything again at this interval
+    #[arg(long, value_name = "SECONDS")]
+    pub refresh: Option<u64>,
+    /// Give up on slow probes after this long
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+    /// Show this text file as the logo
+    #[arg(long, value_name = "PATH")]
+    pub logo: Option<PathBuf>,
+    /// Describe the shell and terminal of this process
+    #[arg(long, value_name = "PID")]
+    pub pid: Option<u32>,
+    /// Show made-up sample data
+    #[arg(long)]
+    pub demo: bool,
+    /// Show sample data animated from this seed
+    #[arg(long, value_name = "SEED")]
+    pub demo_seed: Option<u64>,
+    /// Update CPU and memory usage every second
+    #[arg(long)]
+    pub watch: bool,
+    /// Draw memory, swap and disk usage as gauges
+    #[arg(long)]
+    pub gauges: bool,
+    /// Only collect OS, kernel, uptime, memory and swap
+    #[arg(long)]
+    pub fast: bool,
+    /// Count available package updates
+    #[arg(long)]
+    pub check_updates: bool,
+    /// Look up the public IP address
+    #[arg(long)]
+    pub public_ip: bool,
+    /// Never run external commands
+    #[arg(long)]
+    pub no_exec: bool,
+    /// Leave emoji out of labels
+    #[arg(long)]
+    pub no_emoji: bool,
+    /// Hide user, host and addresses
+    #[arg(long)]
+    pub anonymize: bool,
+    /// Start with IP addresses hidden
+    #[arg(long)]
+    pub hide_ip: bool,
+    /// Derive the colors from the wallpaper
+    #[arg(long)]
+    pub theme_from_wallpaper: bool,
+    /// Show temperatures in Fahrenheit
+    #[arg(long)]
+    pub fahrenheit: bool,
+    /// Mirror the layout for right-to-left locales
+    #[arg(long)]
+    pub rtl: bool,
+    /// List skipped configuration and failed probes
+    #[arg(long)]
+    pub verbose: bool,
+}
+
+impl Cli {
+    /// Apply command line flags on top of the loaded configuration
+    pub fn apply(&self, config: &mut Config) -> Mode {
+        for (set, option) in [
+            (self.check_updates, &mut config.check_updates),
+            (self.public_ip, &mut config.public_ip),
+            (self.no_exec, &mut config.no_exec),
+            (self.no_emoji, &mut config.no_emoji),
+            (self.anonymize, &mut config.anonymize),
+            (self.hide_ip, &mut config.hide_ip),
+            (self.watch, &mut config.watch),
+            (self.gauges, &mut config.gauges),
+            (self.theme_from_wallpaper, &mut config.theme_from_wallpaper),
+            (self.fast, &mut config.fast),
+            (self.verbose, &mut config.verbose),
+            (self.rtl, &mut config.rtl),
+        ] {
+            if set {
+                *option = true;
+            }
+        }
+        if self.fahrenheit {
+            config.temperature_unit = TemperatureUnit::Fahrenheit;
+        }
+
+        if let Some(pid) = self.pid {
+            config.pid = Some(pid);
+        }
+        if let Some(seed) = self.demo_seed {
+            config.demo_seed = Some(seed);
+        } else if self.demo {
+            config.demo_seed.get_or_insert(demo::DEFAULT_SEED);
+        }
+        if let Some(icons) = self.icons {
+            config.icon_mode = Some(icons);
+        }
+        if let Some(version) = self.ip_version {
+            config.ip_version = version;
+        }
+        if let Some(secs) = self.refresh {
+            config.refresh = secs;
+        }
+        if let Some(theme) = self.theme {
+            config.theme = theme;
+        }
+        if let Some(secs) = self.timeout {
+            config.timeout = secs;
+        }
+        if let Some(fields) = &self.fields {
+            config.fields = Some(
+                fields
+                    .split(',')
+                    .map(|key| key.trim().to_string())
+                    .filter(|key| !key.is_empty())
+                    .collect(),
+            );
+        }
+        if let Some(path) = &self.logo {
+            config.logo = Some(path.clone());
+        }
+        if let Some(path) = &self.output {
+            config.output = Some(path.clone());
+        }
+
+        let mode = if self.fields_json {
+            Mode::FieldsJson
+        } else if let Some(path) = &self.svg {
+            Mode::Svg(path.clone())
+        } else if self.print {
+            Mode::Print
+        } else if self.compact {
+            Mode::Compact
+        } else {
+            match self.format {
+                Some(Format::Json) => Mode::Json,
+                Some(Format::Yaml) => Mode::Yaml,
+                Some(Format::Markdown) => Mode::Markdown,
+                Some(Format::Kv) => Mode::Kv,
+                Some(Format::Tui) | None => Mode::Tui,
+            }
+        };
+        // A file can't hold the interactive UI, so write plain text instead
+        if mode == Mode::Tui && config.output.is_some() {
+            Mode::Kv
+        } else {
+            mode
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    fn apply(args: &[&str]) -> (Config, Mode) {
+        let cli = Cli::try_parse_from(std::iter::once("rsysfetch").chain(args.iter().copied()))
+            .expect("arguments should parse");
+        let mut config = Config::default();
+        let mode = cli.apply(&mut config);
+        (config, mode)
+    }
+
+    #[test]
+    fn command_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn format_aliases_pick_the_same_mode() {
+        assert_eq!(apply(&[]).1, Mode::Tui);
+        assert_eq!(apply(&["--format", "md"]).1, Mode::Markdown);
+        assert_eq!(apply(&["--format", "markdown"]).1, Mode::Markdown);
+        assert_eq!(apply(&["--format", "text"]).1, Mode::Kv);
+        assert_eq!(apply(&["--format", "json"]).1, Mode::Json);
+        assert!(Cli::try_parse_from(["rsysfetch", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn output_file_falls_back_to_plain_text() {
+        assert_eq!(apply(&["-o", "out.txt"]).1, Mode::Kv);
+        assert_eq!(apply(&["-o", "out.json", "--format", "json"]).1, Mode::Json);
+    }
+
+    #[test]
+    fn options_override_the_config() {
+        let (config, mode) = apply(&[
+            "--ip-version",
+            "6",
+            "--icons",
+            "nerd",
+            "--theme",
+            "gruvbox",
+            "--fields",
+            "os, cpu,,memory",
+            "--no-exec",
+            "--demo",
+            "-p",
+        ]);
+        assert_eq!(mode, Mode::Print);
+        assert_eq!(config.ip_version, IpVersion::V6);
+        assert_eq!(config.icon_mode, Some(IconMode::Nerd));
+        assert_eq!(config.theme, ThemePreset::Gruvbox);
+        assert_eq!(
+            config.fields,
+            Some(vec![
+                "os".to_string(),
+                "cpu".to_string(),
+                "memory".to_string()
+            ])
+        );
+        assert!(config.no_exec);
+        assert_eq!(config.demo_seed, Some(demo::DEFAULT_SEED));
+    }
+}
@@ -0,0 +1,513 @@
+use std::net::IpAddr;
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::Instant;
+
+use local_ip_address::list_afinet_netifas;
+use sysinfo::{
+    CpuRefreshKind, MINIMUM_CPU_UPDATE_INTERVAL, MemoryRefreshKind, RefreshKind, System,
+};
+
+use crate::config::IpVersion;
+use crate::system_info::{
+    BatteryInfo, CpuInfo, GpuInfo, apply_amd_usage, apply_base_frequency, apply_cpu_usage,
+    apply_frequency_range, apply_nvidia_power_draw, apply_nvidia_usage, collect_cpu_info,
+    default_route_ip, get_battery_info, get_gpu_info_list, read_cpu_list,
+};
+
+/// Memory and swap figures in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    pub total: u64,
+    pub used: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+/// Source of the CPU, GPU, memory, battery, network and uptime readings behind
+/// `SystemInfo::collect_from`, so they can be replaced by fixed values
+pub trait Collector: Send + Sync {
+    /// CPU models with core counts and clocks; `cpu_usage` fills in usage
+    fn cpus(&self) -> Vec<CpuInfo>;
+
+    /// Set the usage of `cpus` and return the overall and per-core usage,
+    /// sampled since the collector was created
+    fn cpu_usage(&self, cpus: &mut [CpuInfo]) -> (f32, Vec<f32>);
+
+    /// GPUs with their usage, and their power draw when `power_draw` is set
+    fn gpus(&self, no_exec: bool, power_draw: bool) -> Vec<GpuInfo>;
+
+    fn memory(&self) -> MemoryUsage;
+
+    /// Charge of the batteries, `None` without any
+    fn battery(&self, no_exec: bool) -> Option<BatteryInfo>;
+
+    /// Addresses of the network interfaces, by interface name
+    fn addresses(&self) -> Vec<(String, IpAddr)>;
+
+    /// The address the default route leaves from, in the requested family
+    fn default_ip(&self, version: IpVersion) -> Result<IpAddr, String>;
+
+    /// Seconds since boot
+    fn uptime(&self) -> u64;
+
+    /// Boot time as Unix seconds
+    fn boot_time(&self) -> u64;
+}
+
+/// Readings from the running system
+#[derive(Debug)]
+pub struct SystemCollector {
+    sys: Mutex<System>,
+    /// CPU usage is the difference between two samples; this is the first
+    first_sample: Instant,
+}
+
+impl SystemCollector {
+    pub fn new() -> Self {
+        // Only refresh what is displayed; enumerating processes is the slow part
+        let sys = System::new_with_specifics(
+            RefreshKind::new()
+                .with_cpu(CpuRefreshKind::everything())
+                .with_memory(MemoryRefreshKind::everything()),
+        );
+        Self {
+            sys: Mutex::new(sys),
+            first_sample: Instant::now(),
+        }
+    }
+
+    fn sys(&self) -> MutexGuard<'_, System> {
+        // The readings stay valid even if a panicking thread held the lock
+        self.sys
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for SystemCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for SystemCollector {
+    fn cpus(&self) -> Vec<CpuInfo> {
+        let sys = self.sys();
+        let mut cpus = collect_cpu_info(&sys);
+        apply_base_frequency(&mut cpus);
+        apply_frequency_range(&mut cpus, &sys);
+        // Offlined CPUs are missing from the usage counters, so take the
+        // total from the kernel's list of present CPUs
+        if let ([cpu], Some(present)) = (cpus.as_mut_slice(), read_cpu_list("present")) {
+            cpu.cores = cpu.cores.max(present);
+        }
+        // The physical count is system-wide, so only pair it with a single model
+        if let [cpu] = cpus.as_mut_slice() {
+            cpu.physical_cores = sys.physical_core_count();
+        }
+        cpus
+    }
+
+    fn cpu_usage(&self, cpus: &mut [CpuInfo]) -> (f32, Vec<f32>) {
+        // Other probes usually outlast the minimum interval, so this
+        // rarely sleeps
+        thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL.saturating_sub(self.first_sample.elapsed()));
+        let mut sys = self.sys();
+        sys.refresh_cpu_usage();
+        apply_cpu_usage(cpus, &sys);
+        let global_usage = sys.global_cpu_info().cpu_usage();
+        let core_usage = sys.cpus().iter().map(|core| core.cpu_usage()).collect();
+        (global_usage, core_usage)
+    }
+
+    fn gpus(&self, no_exec: bool, power_draw: bool) -> Vec<GpuInfo> {
+        let mut gpus = get_gpu_info_list(no_exec);
+        apply_nvidia_usage(&mut gpus, no_exec);
+        apply_amd_usage(&mut gpus);
+        if power_draw {
+            apply_nvidia_power_draw(&mut gpus, no_exec);
+        }
+        gpus
+    }
+
+    fn memory(&self) -> MemoryUsage {
+        let sys = self.sys();
+        MemoryUsage {
+            total: sys.total_memory(),
+            used: sys.used_memory(),
+            swap_total: sys.total_swap(),
+            swap_used: sys.used_swap(),
+        }
+    }
+
+    fn battery(&self, no_exec: bool) -> Option<BatteryInfo> {
+        get_battery_info(no_exec)
+    }
+
+    fn addresses(&self) -> Vec<(String, IpAddr)> {
+        list_afinet_netifas().unwrap_or_default()
+    }
+
+    fn default_ip(&self, version: IpVersion) -> Result<IpAddr, String> {
+        default_route_ip(version).map_err(|err| err.to_string())
+    }
+
+    fn uptime(&self) -> u64 {
+        System::uptime()
+    }
+
+    fn boot_time(&self) -> u64 {
+        System::boot_time()
+    }
+}
+
+/// Fixed readings, returned as given
+#[derive(Debug, Clone, Default)]
+pub struct MockCollector {
+    /// Returned by `cpus`, usage included
+    pub cpus: Vec<CpuInfo>,
+    /// Per-core usage; the overall usage is their average
+    pub core_usage: Vec<f32>,
+    pub gpus: Vec<GpuInfo>,
+    pub memory: MemoryUsage,
+    pub battery: Option<BatteryInfo>,
+    pub addresses: Vec<(String, IpAddr)>,
+    /// Returned for every address family; `None` is reported as no route
+    pub default_ip: Option<IpAddr>,
+    pub uptime: u64,
+    pub boot_time: u64,
+}
+
+impl Collector for MockCollector {
+    fn cpus(&self) -> Vec<CpuInfo> {
+        self.cpus.clone()
+    }
+
+    fn cpu_usage(&self, _cpus: &mut [CpuInfo]) -> (f32, Vec<f32>) {
+        let global_usage = if self.core_usage.is_empty() {
+            0.0
+        } else {
+            self.core_usage.iter().sum::<f32>() / self.core_usage.len() as f32
+        };
+        (global_usage, self.core_usage.clone())
+    }
+
+    fn gpus(&self, _no_exec: bool, _power_draw: bool) -> Vec<GpuInfo> {
+        self.gpus.clone()
+    }
+
+    fn memory(&self) -> MemoryUsage {
+        self.memory
+    }
+
+    fn battery(&self, _no_exec: bool) -> Option<BatteryInfo> {
+        self.battery.clone()
+    }
+
+    fn addresses(&self) -> Vec<(String, IpAddr)> {
+        self.addresses.clone()
+    }
+
+    fn default_ip(&self, _version: IpVersion) -> Result<IpAddr, String> {
+        self.default_ip
+            .ok_or_else(|| "no default route".to_string())
+    }
+
+    fn uptime(&self) -> u64 {
+        self.uptime
+    }
+
+    fn boot_time(&self) -> u64 {
+        self.boot_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::system_info::SystemInfo;
+
+    /// Which reading `Hanging` never returns in time
+    #[derive(PartialEq)]
+    enum Stall {
+        Gpus,
+        CpuUsage,
+    }
+
+    /// Answers like its mock, except for one reading that hangs
+    struct Hanging(MockCollector, Stall);
+
+    impl Hanging {
+        fn stall(&self, reading: Stall) {
+            if self.1 == reading {
+                thread::sleep(Duration::from_secs(30));
+            }
+        }
+    }
+
+    impl Collector for Hanging {
+        fn cpus(&self) -> Vec<CpuInfo> {
+            self.0.cpus()
+        }
+
+        fn cpu_usage(&self, cpus: &mut [CpuInfo]) -> (f32, Vec<f32>) {
+            self.stall(Stall::CpuUsage);
+            self.0.cpu_usage(cpus)
+        }
+
+        fn gpus(&self, no_exec: bool, power_draw: bool) -> Vec<GpuInfo> {
+            self.stall(Stall::Gpus);
+            self.0.gpus(no_exec, power_draw)
+        }
+
+        fn memory(&self) -> MemoryUsage {
+            self.0.memory()
+        }
+
+        fn battery(&self, no_exec: bool) -> Option<BatteryInfo> {
+            self.0.battery(no_exec)
+        }
+
+        fn addresses(&self) -> Vec<(String, IpAddr)> {
+            self.0.addresses()
+        }
+
+        fn default_ip(&self, version: IpVersion) -> Result<IpAddr, String> {
+            self.0.default_ip(version)
+        }
+
+        fn uptime(&self) -> u64 {
+            self.0.uptime()
+        }
+
+        fn boot_time(&self) -> u64 {
+            self.0.boot_time()
+        }
+    }
+
+    fn gpu(name: &str, vendor: &str) -> GpuInfo {
+        GpuInfo {
+            name: name.to_string(),
+            vendor: vendor.to_string(),
+            ..GpuInfo::unknown()
+        }
+    }
+
+    fn collect(collector: MockCollector, config: &Config) -> SystemInfo {
+        SystemInfo::collect_from(config, Arc::new(collector)).unwrap()
+    }
+
+    fn offline() -> Config {
+        Config {
+            no_exec: true,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn processes_are_only_enumerated_on_request() {
+        assert!(SystemCollector::new().sys().processes().is_empty());
+
+        let config = Config {
+            count_processes: false,
+            ..offline()
+        };
+        let info = collect(MockCollector::default(), &config);
+        assert_eq!(info.process_count, None);
+        assert_eq!(info.running_process_count, None);
+
+        if cfg!(target_os = "linux") {
+            let info = collect(MockCollector::default(), &offline());
+            assert!(info.process_count.is_some_and(|count| count > 0));
+        }
+    }
+
+    #[test]
+    fn readings_come_from_the_collector() {
+        let collector = MockCollector {
+            core_usage: vec![20.0, 40.0],
+            memory: MemoryUsage {
+                total: 16 << 30,
+                used: 4 << 30,
+                swap_total: 2 << 30,
+                swap_used: 0,
+            },
+            boot_time: 1_700_000_000,
+            ..MockCollector::default()
+        };
+        let info = collect(collector, &offline());
+
+        assert_eq!(info.global_usage, 30.0);
+        assert_eq!(info.core_usage, [20.0, 40.0]);
+        assert_eq!(info.memory_total, 16 << 30);
+        assert_eq!(info.memory_used, 4 << 30);
+        assert_eq!(info.swap_total, 2 << 30);
+        assert_eq!(info.boot_time, 1_700_000_000);
+    }
+
+    // Parsing the probes' output is tested in system_info; this only checks
+    // that collection passes the collector's GPUs through untouched
+    #[test]
+    fn collector_gpus_are_passed_through() {
+        let collector = MockCollector {
+            gpus: vec![
+                gpu("Intel UHD Graphics 770", "Intel"),
+                gpu("NVIDIA GeForce RTX 4070", "NVIDIA"),
+            ],
+            ..MockCollector::default()
+        };
+        let info = collect(collector, &offline());
+
+        let names: Vec<&str> = info.gpus.iter().map(|gpu| gpu.name.as_str()).collect();
+        assert_eq!(names, ["Intel UHD Graphics 770", "NVIDIA GeForce RTX 4070"]);
+        assert!(info.warnings.iter().all(|warning| warning.field != "gpus"));
+    }
+
+    #[test]
+    fn missing_battery_is_none() {
+        let info = collect(MockCollector::default(), &offline());
+        assert!(info.battery.is_none());
+
+        let collector = MockCollector {
+            battery: Some(BatteryInfo {
+                percentage: 81,
+                charging: true,
+                time_remaining: None,
+            }),
+            ..MockCollector::default()
+        };
+        let battery = collect(collector, &offline()).battery.unwrap();
+        assert_eq!(battery.percentage, 81);
+        assert!(battery.charging);
+    }
+
+    #[test]
+    fn uptime_is_formatted_at_its_boundaries() {
+        let uptime = |seconds, show_seconds| {
+            let collector = MockCollector {
+                uptime: seconds,
+                ..MockCollector::default()
+            };
+            let config = Config {
+                show_seconds,
+                ..offline()
+            };
+            let info = collect(collector, &config);
+            assert_eq!(info.uptime_seconds, seconds);
+            info.uptime
+        };
+
+        assert_eq!(uptime(59, false), "0m");
+        assert_eq!(uptime(59, true), "59s");
+        assert_eq!(uptime(3599, true), "59m 59s");
+        assert_eq!(uptime(3600, true), "1h 0m");
+        assert_eq!(uptime(86400, false), "1d 0h 0m");
+    }
+
+    #[test]
+    fn timeout_returns_the_values_gathered_so_far() {
+        let collector = Hanging(
+            MockCollector {
+                memory: MemoryUsage {
+                    total: 16 << 30,
+                    used: 4 << 30,
+                    ..MemoryUsage::default()
+                },
+                uptime: 7200,
+                gpus: vec![gpu("NVIDIA GeForce RTX 4070", "NVIDIA")],
+                ..MockCollector::default()
+            },
+            Stall::Gpus,
+        );
+        let info = SystemInfo::collect_with_timeout(
+            &offline(),
+            Arc::new(collector),
+            Duration::from_millis(500),
+        )
+        .unwrap();
+
+        let timeout = info
+            .warnings
+            .iter()
+            .find(|warning| warning.field == "*")
+            .expect("a timeout warning");
+        assert!(timeout.message.starts_with("collection timed out"));
+        // Everything before the GPU probe was joined made it through
+        assert_eq!(info.memory_total, 16 << 30);
+        assert_eq!(info.uptime, "2h 0m");
+        assert_ne!(info.shell, "Unknown Shell");
+        assert!(info.gpus.is_empty());
+    }
+
+    #[test]
+    fn fallbacks_are_listed_in_the_json_warnings() {
+        let json = |info: &SystemInfo| -> serde_json::Value {
+            serde_json::from_str(&crate::fields::info_to_json(info).unwrap()).unwrap()
+        };
+        assert_eq!(
+            json(&SystemInfo::sample())["warnings"],
+            serde_json::json!([])
+        );
+
+        // Commands are allowed, or an unknown GPU is expected; the CPU
+        // usage is sampled after every probe is joined
+        let collector = Hanging(
+            MockCollector {
+                gpus: vec![GpuInfo::unknown()],
+                default_ip: None,
+                ..MockCollector::default()
+            },
+            Stall::CpuUsage,
+        );
+        let info = SystemInfo::collect_with_timeout(
+            &Config::default(),
+            Arc::new(collector),
+            Duration::from_secs(3),
+        )
+        .unwrap();
+
+        let warnings = json(&info)["warnings"].as_array().unwrap().clone();
+        let warning = |field: &str| {
+            warnings
+                .iter()
+                .find(|warning| warning["field"] == field)
+                .unwrap_or_else(|| panic!("no warning for {field} in {warnings:?}"))["message"]
+                .as_str()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(warning("gpus"), "no GPU found by the platform probe");
+        assert_eq!(warning("local_ip"), "could not resolve: no default route");
+        assert!(warning("*").starts_with("collection timed out after 3s"));
+        assert_eq!(info.local_ip, "Unknown IP");
+    }
+
+    #[test]
+    fn primary_interface_overrides_the_default_route() {
+        let collector = || MockCollector {
+            addresses: vec![
+                ("eth0".to_string(), "192.168.1.20".parse().unwrap()),
+                ("eth1".to_string(), "10.0.0.5".parse().unwrap()),
+            ],
+            default_ip: Some("192.168.1.20".parse().unwrap()),
+            ..MockCollector::default()
+        };
+        let local_ip = |interface: Option<&str>| {
+            let config = Config {
+                primary_interface: interface.map(str::to_string),
+                ..offline()
+            };
+            collect(collector(), &config).local_ip
+        };
+
+        assert_eq!(local_ip(None), "192.168.1.20");
+        assert_eq!(local_ip(Some("eth1")), "10.0.0.5");
+        // A missing interface falls back to the default route
+        assert_eq!(local_ip(Some("eth9")), "192.168.1.20");
+    }
+}
@@ -0,0 +1,639 @@
+use crate::AppResult;
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde::de::{self, Visitor};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// User configuration loaded from `config.toml`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Decimal places for percentages
+    pub percent_precision: usize,
+    /// Decimal places for frequencies
+    pub freq_precision: usize,
+    /// Query the package manager for pending updates
+    pub check_updates: bool,
+    /// Look up the public IP address through an online resolver
+    pub public_ip: bool,
+    /// Never spawn external commands during collection
+    pub no_exec: bool,
+    /// Replace emoji and unicode art with plain ASCII
+    pub no_emoji: bool,
+    /// Ordering of the disk list
+    pub disk_sort: DiskSort,
+    /// Show a short summary in the terminal title while running
+    pub set_title: bool,
+    /// Per-section icon overrides from the `[icons]` table
+    pub icons: HashMap<String, String>,
+    /// Optional sections to show in addition to the defaults
+    pub show: Vec<String>,
+    /// Default sections to leave out
+    pub hide: Vec<String>,
+    /// Exactly these sections in this order, replacing the defaults and
+    /// `show`
+    pub fields: Option<Vec<String>>,
+    /// How integer counts are written
+    pub number_format: NumberFormat,
+    /// Format of the memory and disk usage lines
+    pub memory_display: MemoryDisplay,
+    /// Unit system of byte sizes
+    pub byte_units: ByteUnits,
+    /// Mask hostname, username, machine ID, addresses and the username in paths
+    pub anonymize: bool,
+    /// Start with network addresses hidden
+    pub hide_ip: bool,
+    /// Keep refreshing CPU and memory figures while running
+    pub watch: bool,
+    /// Collect everything again every this many seconds while running;
+    /// 0 disables it
+    pub refresh: u64,
+    /// Draw a border around the info panel
+    pub show_border: bool,
+    /// Sysctl keys to read from `/proc/sys` on Linux
+    pub sysctls: Vec<String>,
+    /// Color preset of the panel
+    pub theme: ThemePreset,
+    /// Derive the panel colors from the desktop wallpaper (experimental)
+    pub theme_from_wallpaper: bool,
+    /// How sections with several items (CPUs, GPUs, disks) are laid out
+    pub repeat_layout: RepeatLayout,
+    /// Sample CPU and GPU power draw at startup
+    pub power_draw: bool,
+    /// Unit of temperature readings
+    pub temperature_unit: TemperatureUnit,
+    /// Start with the per-core usage panel open; toggled with 'c'
+    pub cpu_cores: bool,
+    /// Draw memory, swap and disk usage as gauges in the TUI
+    pub gauges: bool,
+    /// Describe the shell and terminal of this process instead of our own;
+    /// only set from the command line
+    #[serde(skip)]
+    pub pid: Option<u32>,
+    /// Show made-up sample data animated from this seed; only set from
+    /// the command line
+    #[serde(skip)]
+    pub demo_seed: Option<u64>,
+    /// Write the non-interactive output to this file instead of stdout;
+    /// only set from the command line
+    #[serde(skip)]
+    pub output: Option<PathBuf>,
+    /// Enumerate processes for the process counts, the slowest probe;
+    /// turned off by the binary when the processes section isn't shown
+    #[serde(skip)]
+    pub count_processes: bool,
+    /// Latency preset for shell prompts: only OS, kernel, uptime, memory
+    /// and swap
+    pub fast: bool,
+    /// Upper bound in seconds for the whole collection; 0 disables it
+    pub timeout: u64,
+    /// Report probes that failed during collection on stderr
+    pub verbose: bool,
+    /// Mirror the layout: logo on the right, info right-aligned
+    pub rtl: bool,
+    /// Briefly mark values that changed since the previous launch
+    pub highlight_changes: bool,
+    /// Include seconds in uptimes under an hour
+    pub show_seconds: bool,
+    /// Interface whose address is shown as the local IP, e.g. "eth0"
+    pub primary_interface: Option<String>,
+    /// Address family of the local IP
+    pub ip_version: IpVersion,
+    /// List the loopback interface in the interfaces panel
+    pub show_loopback: bool,
+    /// ASCII/ANSI art file shown instead of the built-in logo
+    pub logo: Option<PathBuf>,
+    /// Icon set to use; otherwise picked from the terminal's capabilities
+    pub icon_mode: Option<IconMode>,
+    /// Problems with the configuration files that were skipped over,
+    /// reported with `--verbose`
+    #[serde(skip)]
+    pub load_warnings: Vec<String>,
+}
+
+/// Which family of icons the labels use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum IconMode {
+    Emoji,
+    Nerd,
+    Ascii,
+}
+
+/// Disk list ordering
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiskSort {
+    /// Alphabetical by mount point
+    #[default]
+    Mount,
+    /// Highest usage percentage first
+    Usage,
+    /// Largest capacity first
+    Size,
+}
+
+/// Formatting of integer counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberFormat {
+    /// 1432
+    #[default]
+    Plain,
+    /// 1,432
+    Grouped,
+    /// 1.4k
+    Si,
+}
+
+/// Format of usage lines
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryDisplay {
+    /// 512.0MiB / 2.0GiB (25%)
+    #[default]
+    Full,
+    /// 25%
+    Percent,
+    /// 512.0MiB / 2.0GiB
+    Absolute,
+}
+
+/// Built-in color presets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    /// Cyan labels and yellow list items
+    #[default]
+    Default,
+    /// The terminal's own colors only
+    Monochrome,
+    Dracula,
+    Gruvbox,
+}
+
+/// Unit system of byte sizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ByteUnits {
+    /// Powers of 1024: KiB, MiB, GiB, TiB
+    #[default]
+    Binary,
+    /// Powers of 1000: KB, MB, GB, TB
+    Decimal,
+}
+
+/// Layout of sections with several items
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RepeatLayout {
+    /// One item per line
+    #[default]
+    List,
+    /// Items packed into up to three columns
+    Grid,
+}
+
+/// Address family of the local IP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+pub enum IpVersion {
+    /// IPv4, falling back to IPv6 when there is no IPv4 route
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "4")]
+    #[value(name = "4")]
+    V4,
+    #[serde(rename = "6")]
+    #[value(name = "6")]
+    V6,
+}
+
+/// Unit of temperature readings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            percent_precision: 0,
+            freq_precision: 2,
+            check_updates: false,
+            public_ip: false,
+            no_exec: false,
+            no_emoji: false,
+            disk_sort: DiskSort::Mount,
+            set_title: false,
+            icons: HashMap::new(),
+            show: Vec::new(),
+            hide: Vec::new(),
+            fields: None,
+            number_format: NumberFormat::Plain,
+            memory_display: MemoryDisplay::Full,
+            byte_units: ByteUnits::Binary,
+            anonymize: false,
+            hide_ip: false,
+            watch: false,
+            refresh: 0,
+            show_border: true,
+            sysctls: Vec::new(),
+            theme: ThemePreset::Default,
+            theme_from_wallpaper: false,
+            repeat_layout: RepeatLayout::List,
+            power_draw: false,
+            temperature_unit: TemperatureUnit::Celsius,
+            cpu_cores: false,
+            gauges: false,
+            pid: None,
+            demo_seed: None,
+            output: None,
+            count_processes: true,
+            fast: false,
+            timeout: 10,
+            verbose: false,
+            rtl: false,
+            highlight_changes: false,
+            show_seconds: false,
+            primary_interface: None,
+            ip_version: IpVersion::Auto,
+            show_loopback: false,
+            logo: None,
+            icon_mode: None,
+            load_warnings: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Load configuration, falling back to defaults when missing or invalid.
+    /// A malformed base file, unknown keys and invalid values are skipped
+    /// with a warning in `load_warnings`. A named profile
+    /// (`config.<name>.toml`) is layered over the base file and, unlike the
+    /// base file, must exist and parse.
+    pub fn load(profile: Option<&str>) -> AppResult<Self> {
+        Self::load_from(config_dir().as_deref(), profile)
+    }
+
+    /// `load` with the files looked up in `dir`
+    fn load_from(dir: Option<&Path>, profile: Option<&str>) -> AppResult<Self> {
+        let mut warnings = Vec::new();
+        let base = dir
+            .map(|dir| read_table(&dir.join("config.toml"), &mut warnings))
+            .unwrap_or_default();
+        let base = valid_keys(base, &mut warnings);
+
+        let Some(profile) = profile else {
+            let mut config: Self = base.try_into().unwrap_or_default();
+            warnings.extend(config.icon_warnings());
+            config.load_warnings = warnings;
+            return Ok(config);
+        };
+
+        let path = dir
+            .ok_or("cannot determine the configuration directory")?
+            .join(format!("config.{profile}.toml"));
+        let content = fs::read_to_string(&path).map_err(|err| {
+            format!(
+                "cannot read profile '{profile}' at {}: {err}",
+                path.display()
+            )
+        })?;
+        let overlay = content
+            .parse::<Table>()
+            .map_err(|err| format!("invalid profile '{profile}': {err}"))?;
+
+        let mut config = Self::from_layers(base, overlay)
+            .map_err(|err| format!("invalid profile '{profile}': {err}"))?;
+        warnings.extend(config.icon_warnings());
+        config.load_warnings = warnings;
+        Ok(config)
+    }
+
+    /// Build a configuration from a base table overridden by another
+    fn from_layers(mut base: Table, overlay: Table) -> Result<Self, toml::de::Error> {
+        merge_tables(&mut base, overlay);
+        base.try_into()
+    }
+
+    /// Icon override for a section, ignoring values that can't be a single glyph
+    pub fn icon(&self, section: &str) -> Option<&str> {
+        self.icons
+            .get(section)
+            .map(|icon| icon.trim())
+            .filter(|icon| is_valid_icon(icon))
+    }
+
+    /// A warning for every `[icons]` value that `icon` ignores
+    fn icon_warnings(&self) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .icons
+            .iter()
+            .filter(|(_, icon)| !is_valid_icon(icon.trim()))
+            .map(|(section, icon)| {
+                format!("ignoring icon '{icon}' for '{section}': not a single glyph")
+            })
+            .collect();
+        // In a stable order, unlike the map
+        warnings.sort();
+        warnings
+    }
+
+    /// Whether to render in ASCII mode, either requested or forced by the console
+    pub fn ascii_mode(&self) -> bool {
+        self.no_emoji || !unicode_supported()
+    }
+}
+
+/// Parse a TOML file; a missing file is an empty table, an unreadable or
+/// malformed one too but with a warning
+fn read_table(path: &Path, warnings: &mut Vec<String>) -> Table {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Table::new(),
+        Err(err) => {
+            warnings.push(format!("cannot read {}: {err}", path.display()));
+            return Table::new();
+        }
+    };
+    content.parse().unwrap_or_else(|err: toml::de::Error| {
+        warnings.push(format!(
+            "ignoring {}: {}",
+            path.display(),
+            err.message().trim()
+        ));
+        Table::new()
+    })
+}
+
+/// Drop unknown keys and keys whose value doesn't deserialize, so one
+/// mistake doesn't throw away the rest of the file
+fn valid_keys(mut table: Table, warnings: &mut Vec<String>) -> Table {
+    let known = config_keys();
+    table.retain(|key, value| {
+        if !known.contains(&key) {
+            warnings.push(format!("unknown config key '{key}'"));
+            return false;
+        }
+        let single = Table::from_iter([(key.to_string(), value.clone())]);
+        match single.try_into::<Config>() {
+            Ok(_) => true,
+            Err(err) => {
+                warnings.push(format!(
+                    "ignoring config key '{key}': {}",
+                    err.message().trim()
+                ));
+                false
+            }
+        }
+    });
+    table
+}
+
+/// Keys accepted in the config file, taken from the `Deserialize` impl so
+/// the list can't drift from the struct
+fn config_keys() -> &'static [&'static str] {
+    /// Deserializer that only records the field names serde asks for
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(de::Error::custom("only struct fields are recorded"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _: &'static str,
+            fields: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("only struct fields are recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = Config::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Loosely check that an icon is a single glyph: emoji sequences span
+/// several code points, so only reject obviously long or blank values
+fn is_valid_icon(icon: &str) -> bool {
+    let count = icon.chars().count();
+    (1..=8).contains(&count) && !icon.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+/// Guess whether the console can render unicode
+fn unicode_supported() -> bool {
+    unicode_supported_with(|name| env::var(name).ok())
+}
+
+/// `unicode_supported` with the environment read through `var`
+fn unicode_supported_with(var: impl Fn(&str) -> Option<String>) -> bool {
+    // The Linux virtual console only has a limited glyph set
+    if var("TERM").is_some_and(|term| term == "linux") {
+        return false;
+    }
+
+    if cfg!(target_os = "windows") {
+        return true;
+    }
+
+    match ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|name| var(name).filter(|value| !value.is_empty()))
+    {
+        Some(locale) => {
+            let locale = locale.to_uppercase();
+            locale.contains("UTF-8") || locale.contains("UTF8")
+        }
+        None => true,
+    }
+}
+
+/// Recursively merge `overlay` into `base`, with `overlay` winning
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Get the configuration directory
+fn config_dir() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join("sysfetch"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn linux_console_falls_back_to_ascii() {
+        assert!(!unicode_supported_with(env(&[
+            ("TERM", "linux"),
+            ("LANG", "en_US.UTF-8"),
+        ])));
+        assert!(unicode_supported_with(env(&[
+            ("TERM", "xterm-256color"),
+            ("LANG", "en_US.UTF-8"),
+        ])));
+    }
+
+    #[test]
+    fn unicode_follows_the_locale() {
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        assert!(unicode_supported_with(env(&[("LANG", "de_DE.utf8")])));
+        assert!(!unicode_supported_with(env(&[("LANG", "C")])));
+        // LC_ALL wins over LANG, and empty values are skipped
+        assert!(!unicode_supported_with(env(&[
+            ("LC_ALL", "POSIX"),
+            ("LANG", "en_US.UTF-8"),
+        ])));
+        assert!(unicode_supported_with(env(&[
+            ("LC_ALL", ""),
+            ("LANG", "en_US.UTF-8"),
+        ])));
+        assert!(unicode_supported_with(env(&[])));
+    }
+
+    #[test]
+    fn is_valid_icon_accepts_single_glyphs() {
+        for icon in ["C", "🔥", "\u{f108}", "👨‍💻", "🇩🇪", ">_"] {
+            assert!(is_valid_icon(icon), "{icon:?}");
+        }
+        for icon in ["", " ", "two words", "far too long", "\t", "a\u{7}"] {
+            assert!(!is_valid_icon(icon), "{icon:?}");
+        }
+    }
+
+    #[test]
+    fn invalid_icon_overrides_are_ignored_with_a_warning() {
+        let config = Config {
+            icons: HashMap::from([
+                ("cpu".to_string(), " 🔥 ".to_string()),
+                ("gpu".to_string(), "graphics card".to_string()),
+            ]),
+            ..Config::default()
+        };
+        assert_eq!(config.icon("cpu"), Some("🔥"));
+        assert_eq!(config.icon("gpu"), None);
+        assert_eq!(config.icon("os"), None);
+        assert_eq!(
+            config.icon_warnings(),
+            ["ignoring icon 'graphics card' for 'gpu': not a single glyph"]
+        );
+    }
+
+    /// A fresh directory holding the given config files
+    fn config_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rsysfetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, content) in files {
+            fs::write(dir.join(file), content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn merge_tables_merges_nested_tables() {
+        let mut base: Table = "a = 1\nb = 2\n[icons]\ncpu = 'C'\nmemory = 'M'\n"
+            .parse()
+            .unwrap();
+        let overlay: Table = "b = 3\nc = [1]\n[icons]\ncpu = 'X'\n".parse().unwrap();
+        merge_tables(&mut base, overlay);
+
+        let expected: Table = "a = 1\nb = 3\nc = [1]\n[icons]\ncpu = 'X'\nmemory = 'M'\n"
+            .parse()
+            .unwrap();
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn profile_overrides_the_base_file() {
+        let dir = config_files(
+            "profile",
+            &[
+                (
+                    "config.toml",
+                    "percent-precision = 1\ntheme = \"dracula\"\n[icons]\ncpu = \"C\"\nmemory = \"M\"\n",
+                ),
+                (
+                    "config.work.toml",
+                    "percent-precision = 2\n[icons]\ncpu = \"X\"\n",
+                ),
+            ],
+        );
+
+        let base = Config::load_from(Some(&dir), None).unwrap();
+        assert_eq!(base.percent_precision, 1);
+        assert_eq!(base.icon("cpu"), Some("C"));
+
+        let work = Config::load_from(Some(&dir), Some("work")).unwrap();
+        assert_eq!(work.percent_precision, 2);
+        assert!(matches!(work.theme, ThemePreset::Dracula));
+        assert_eq!(work.icon("cpu"), Some("X"));
+        assert_eq!(work.icon("memory"), Some("M"));
+        assert!(work.load_warnings.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_or_malformed_profile_is_an_error() {
+        let dir = config_files("bad-profile", &[("config.broken.toml", "theme = ")]);
+
+        let missing = Config::load_from(Some(&dir), Some("missing")).unwrap_err();
+        assert!(
+            missing
+                .to_string()
+                .starts_with("cannot read profile 'missing'")
+        );
+        let broken = Config::load_from(Some(&dir), Some("broken")).unwrap_err();
+        assert!(broken.to_string().starts_with("invalid profile 'broken'"));
+        // Without a profile, missing files are just the defaults
+        assert!(Config::load_from(Some(&dir), None).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
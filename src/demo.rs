@@ -0,0 +1,62 @@
+use crate::system_info::SystemInfo;
+
+/// Seed used by `--demo` when none is given
+pub const DEFAULT_SEED: u64 = 0x5eed;
+
+/// Deterministic random walk over the sample data, so watch mode looks
+/// alive while every run with the same seed renders the same frames
+#[derive(Debug)]
+pub struct Demo {
+    state: u64,
+}
+
+impl Demo {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on zero
+        Self { state: seed.max(1) }
+    }
+
+    /// Nudge CPU and per-core usage, memory use and network rates by a small
+    /// random amount
+    pub fn step(&mut self, info: &mut SystemInfo) {
+        for cpu in &mut info.cpus {
+            let delta = self.next_signed() * 15.0;
+            cpu.usage = (cpu.usage + delta).clamp(2.0, 98.0);
+        }
+        for usage in &mut info.core_usage {
+            let delta = self.next_signed() * 20.0;
+            *usage = (*usage + delta).clamp(1.0, 99.0);
+        }
+        if !info.cpus.is_empty() {
+            info.global_usage =
+                info.cpus.iter().map(|cpu| cpu.usage).sum::<f32>() / info.cpus.len() as f32;
+        }
+
+        let delta = (self.next_signed() * info.memory_total as f32 * 0.02) as i64;
+        let low = info.memory_total / 8;
+        let high = info.memory_total / 10 * 9;
+        info.memory_used = info
+            .memory_used
+            .saturating_add_signed(delta)
+            .clamp(low, high);
+
+        for interface in &mut info.interfaces {
+            for rate in [&mut interface.rx_rate, &mut interface.tx_rate]
+                .into_iter()
+                .flatten()
+            {
+                let factor = 1.0 + self.next_signed() * 0.3;
+                *rate = (*rate as f32 * factor) as u64;
+            }
+        }
+    }
+
+    /// A value in `-1.0..1.0`
+    fn next_signed(&mut self) -> f32 {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+    }
+}
@@ -0,0 +1,134 @@
+use serde::Serialize;
+
+use crate::system_info::SystemInfo;
+
+/// Version of the serialized data schema, bumped on incompatible changes
+pub const SCHEMA_VERSION: u32 = 1;
+
+const ALL: &[&str] = &["linux", "macos", "windows"];
+const LINUX: &[&str] = &["linux"];
+const UNIX: &[&str] = &["linux", "macos"];
+
+/// Metadata describing one collected field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldMeta {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub ty: &'static str,
+    pub platforms: &'static [&'static str],
+    pub requires_elevation: bool,
+    pub shell_out: bool,
+}
+
+const fn field(
+    name: &'static str,
+    ty: &'static str,
+    platforms: &'static [&'static str],
+    shell_out: bool,
+) -> FieldMeta {
+    FieldMeta {
+        name,
+        ty,
+        platforms,
+        requires_elevation: false,
+        shell_out,
+    }
+}
+
+/// Registry of every field in `SystemInfo`, kept in declaration order
+pub const FIELDS: &[FieldMeta] = &[
+    field("os_name", "string", ALL, false),
+    field("os_version", "string", ALL, false),
+    field("distro_id", "string?", LINUX, false),
+    field("os_arch", "string", ALL, false),
+    field("kernel_version", "string", ALL, false),
+    field("hostname", "string", ALL, false),
+    field("machine_id", "string?", ALL, true),
+    field("machine", "machine?", ALL, true),
+    field("chassis_type", "string?", ALL, true),
+    field("virtualization", "string?", ALL, true),
+    field("username", "string", ALL, false),
+    field("uptime", "string", ALL, false),
+    field("uptime_seconds", "u64", ALL, false),
+    field("boot_time", "u64", ALL, false),
+    field("local_time", "u64", ALL, false),
+    field("locale", "string?", ALL, false),
+    field("timezone", "string?", ALL, false),
+    field("packages", "(string, usize)[]", ALL, true),
+    field("cpus", "cpu[]", ALL, false),
+    field("global_usage", "f32", ALL, false),
+    field("core_usage", "f32[]", ALL, false),
+    field("cores_online", "usize?", LINUX, false),
+    field("cpu_temp", "f32?", UNIX, false),
+    field("turbo_enabled", "bool?", LINUX, false),
+    field("thermal_throttling", "bool?", LINUX, false),
+    field("load_average", "(f64, f64, f64)?", UNIX, false),
+    field("process_count", "usize?", ALL, false),
+    field("running_process_count", "usize?", ALL, false),
+    field("memory_total", "u64", ALL, false),
+    field("memory_used", "u64", ALL, false),
+    field("swap_total", "u64", ALL, false),
+    field("swap_used", "u64", ALL, false),
+    field("zram_ratio", "f32?", LINUX, false),
+    field("battery", "battery?", UNIX, true),
+    field("gpus", "gpu[]", ALL, true),
+    field("displays", "display[]", ALL, true),
+    field("disks", "disk[]", ALL, false),
+    field("local_ip", "string", ALL, false),
+    field("interfaces", "interface[]", ALL, false),
+    field("public_ip", "string?", ALL, true),
+    field("gateway", "string?", ALL, true),
+    field("dns_servers", "string[]", ALL, true),
+    field("vpn_active", "bool", ALL, false),
+    field("vpn_interface", "string?", ALL, false),
+    field("shell", "string", ALL, true),
+    field("shell_version", "string?", UNIX, true),
+    field("terminal", "string", ALL, true),
+    field("cwd", "string?", ALL, false),
+    field("entropy_available", "u32?", LINUX, false),
+    field("open_fds", "usize?", LINUX, false),
+    field("fd_limit", "u64?", LINUX, false),
+    field("sysctls", "[string, string][]", LINUX, false),
+    field("updates_available", "usize?", LINUX, true),
+    field("nerd_font", "bool", ALL, true),
+    field("terminal_caps", "terminal_caps", ALL, false),
+    field("warnings", "warning[]", ALL, false),
+    field("collection_errors", "string[]", ALL, false),
+];
+
+#[derive(Serialize)]
+struct FieldsDocument {
+    schema_version: u32,
+    fields: &'static [FieldMeta],
+}
+
+#[derive(Serialize)]
+struct InfoDocument<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    info: &'a SystemInfo,
+}
+
+/// Render collected values as pretty-printed JSON
+pub fn info_to_json(info: &SystemInfo) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&InfoDocument {
+        schema_version: SCHEMA_VERSION,
+        info,
+    })
+}
+
+/// Render collected values as YAML, with the same fields as the JSON
+pub fn info_to_yaml(info: &SystemInfo) -> serde_yaml::Result<String> {
+    serde_yaml::to_string(&InfoDocument {
+        schema_version: SCHEMA_VERSION,
+        info,
+    })
+}
+
+/// Render the field registry as pretty-printed JSON
+pub fn to_json() -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&FieldsDocument {
+        schema_version: SCHEMA_VERSION,
+        fields: FIELDS,
+    })
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Local, TimeZone};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::{MemoryDisplay, NumberFormat, TemperatureUnit};
+
+/// Compute a usage percentage, treating a zero total as 0%
+pub fn percentage(used: u64, total: u64) -> f64 {
+    if total > 0 {
+        used as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Format a percentage rounded to the given decimal places
+pub fn format_percent(percent: f64, precision: usize) -> String {
+    format!("{percent:.precision$}%")
+}
+
+/// Format a byte count in the largest unit that keeps it at or above one,
+/// with one decimal place, e.g. "15.6GiB" (binary) or "16.8GB" (decimal)
+pub fn format_bytes(bytes: u64, binary: bool) -> String {
+    const BINARY: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    const DECIMAL: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let (units, base) = if binary {
+        (BINARY, 1024.0)
+    } else {
+        (DECIMAL, 1000.0)
+    };
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    // Scale on the rounded value so 1023.95MiB shows as 1.0GiB, not 1024.0MiB
+    let round = |value: f64| (value * 10.0).round_ties_even() / 10.0;
+    while round(value) >= base && unit < units.len() - 1 {
+        value /= base;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{:.1}{}", round(value), units[unit])
+    }
+}
+
+/// Format a used/total pair of byte counts according to the display mode,
+/// e.g. "512.0MiB / 2.0GiB (25%)"
+pub fn format_usage(
+    used: u64,
+    total: u64,
+    binary: bool,
+    display: MemoryDisplay,
+    precision: usize,
+) -> String {
+    let absolute = format!(
+        "{} / {}",
+        format_bytes(used, binary),
+        format_bytes(total, binary)
+    );
+    let percent = format_percent(percentage(used, total), precision);
+    match display {
+        MemoryDisplay::Full => format!("{absolute} ({percent})"),
+        MemoryDisplay::Percent => percent,
+        MemoryDisplay::Absolute => absolute,
+    }
+}
+
+/// Format a temperature given in °C in the configured unit, e.g. "54°C"
+pub fn format_temperature(celsius: f32, unit: TemperatureUnit) -> String {
+    match unit {
+        TemperatureUnit::Celsius => format!("{celsius:.0}°C"),
+        TemperatureUnit::Fahrenheit => format!("{:.0}°F", celsius * 9.0 / 5.0 + 32.0),
+    }
+}
+
+/// Format a Unix timestamp as local date and time, e.g. "2024-06-01 08:14",
+/// or in UTC (marked as such) when the local offset can't be determined
+pub fn format_timestamp(secs: u64) -> String {
+    let secs = secs as i64;
+    match Local.timestamp_opt(secs, 0).single() {
+        Some(local) => local.format("%Y-%m-%d %H:%M").to_string(),
+        None => DateTime::from_timestamp(secs, 0)
+            .map(|utc| utc.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| "Unknown".to_string()),
+    }
+}
+
+/// Format a Unix timestamp as local time of day, e.g. "08:14:05"
+pub fn format_clock(secs: u64) -> String {
+    match Local.timestamp_opt(secs as i64, 0).single() {
+        Some(local) => local.format("%H:%M:%S").to_string(),
+        None => DateTime::from_timestamp(secs as i64, 0)
+            .map(|utc| utc.format("%H:%M:%S UTC").to_string())
+            .unwrap_or_else(|| "Unknown".to_string()),
+    }
+}
+
+/// Format a frequency in MHz as GHz with the given decimal places
+pub fn format_frequency(mhz: u64, precision: usize) -> String {
+    format!("{:.*}GHz", precision, mhz as f64 / 1000.0)
+}
+
+/// Format an integer count as plain, grouped ("1,432") or SI ("1.4k")
+pub fn format_count(count: u64, format: NumberFormat) -> String {
+    match format {
+        NumberFormat::Plain => count.to_string(),
+        NumberFormat::Grouped => {
+            let digits = count.to_string();
+            let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+            for (i, digit) in digits.chars().enumerate() {
+                if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                    grouped.push(',');
+                }
+                grouped.push(digit);
+            }
+            grouped
+        }
+        NumberFormat::Si => {
+            const SUFFIXES: [&str; 6] = ["k", "M", "G", "T", "P", "E"];
+            if count < 1000 {
+                return count.to_string();
+            }
+
+            let mut value = count as f64;
+            let mut suffix = 0;
+            loop {
+                value /= 1000.0;
+                // Move up a unit when rounding would print "1000.0k"
+                if (value * 10.0).round() < 10_000.0 || suffix == SUFFIXES.len() - 1 {
+                    break;
+                }
+                suffix += 1;
+            }
+            format!("{:.1}{}", value, SUFFIXES[suffix])
+        }
+    }
+}
+
+/// Render usage percentages as a one-line sparkline
+pub fn sparkline(values: impl Iterator<Item = f32>, ascii: bool) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    const ASCII_BARS: [char; 8] = ['_', '.', ':', '-', '=', '+', '*', '#'];
+    let bars = if ascii { ASCII_BARS } else { BARS };
+
+    values
+        .map(|value| bars[(value.clamp(0.0, 100.0) / 100.0 * 7.0).round() as usize])
+        .collect()
+}
+
+/// Number of terminal cells a string occupies, counting emoji and CJK as two
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cut a string to at most the given display width
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    let mut used = 0;
+    text.chars()
+        .take_while(|&c| {
+            used += UnicodeWidthChar::width(c).unwrap_or(0);
+            used <= width
+        })
+        .collect()
+}
+
+/// Right-pad a string with spaces to the given display width
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    format!("{text}{}", " ".repeat(padding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_percent_rounds_to_precision() {
+        assert_eq!(format_percent(42.76, 0), "43%");
+        assert_eq!(format_percent(42.76, 1), "42.8%");
+        assert_eq!(format_percent(42.76, 2), "42.76%");
+        assert_eq!(format_percent(42.74, 1), "42.7%");
+        assert_eq!(format_percent(0.0, 2), "0.00%");
+        assert_eq!(format_percent(99.96, 1), "100.0%");
+    }
+
+    #[test]
+    fn format_count_in_each_number_format() {
+        let cases = [
+            (999, "999", "999", "999"),
+            (1432, "1432", "1,432", "1.4k"),
+            (1_400_000, "1400000", "1,400,000", "1.4M"),
+        ];
+        for (count, plain, grouped, si) in cases {
+            assert_eq!(format_count(count, NumberFormat::Plain), plain);
+            assert_eq!(format_count(count, NumberFormat::Grouped), grouped);
+            assert_eq!(format_count(count, NumberFormat::Si), si);
+        }
+    }
+
+    #[test]
+    fn format_count_si_moves_up_instead_of_printing_1000() {
+        assert_eq!(format_count(999_949, NumberFormat::Si), "999.9k");
+        assert_eq!(format_count(999_950, NumberFormat::Si), "1.0M");
+        assert_eq!(format_count(u64::MAX, NumberFormat::Si), "18.4E");
+    }
+
+    #[test]
+    fn format_usage_in_each_memory_display() {
+        const MIB: u64 = 1 << 20;
+        let usage = |display| format_usage(512 * MIB, 2048 * MIB, true, display, 0);
+        assert_eq!(usage(MemoryDisplay::Full), "512.0MiB / 2.0GiB (25%)");
+        assert_eq!(usage(MemoryDisplay::Percent), "25%");
+        assert_eq!(usage(MemoryDisplay::Absolute), "512.0MiB / 2.0GiB");
+    }
+
+    #[test]
+    fn format_usage_with_decimal_units_and_precision() {
+        let usage = format_usage(1_500_000_000, 4_000_000_000, false, MemoryDisplay::Full, 1);
+        assert_eq!(usage, "1.5GB / 4.0GB (37.5%)");
+        // A zero total reads as unused rather than dividing by zero
+        assert_eq!(format_usage(0, 0, true, MemoryDisplay::Percent, 0), "0%");
+    }
+
+    #[test]
+    fn display_width_counts_wide_characters_twice() {
+        assert_eq!(display_width("CPU"), 3);
+        assert_eq!(display_width("💻 CPU"), 6);
+        assert_eq!(display_width("中文"), 4);
+        assert_eq!(display_width(""), 0);
+    }
+
+    #[test]
+    fn truncate_to_width_never_splits_a_wide_character() {
+        assert_eq!(truncate_to_width("💻 CPU", 4), "💻 C");
+        assert_eq!(truncate_to_width("💻 CPU", 1), "");
+        assert_eq!(truncate_to_width("中文字", 3), "中");
+        assert_eq!(truncate_to_width("中文字", 4), "中文");
+        assert_eq!(truncate_to_width("CPU", 10), "CPU");
+    }
+
+    #[test]
+    fn pad_to_width_pads_by_display_width() {
+        assert_eq!(pad_to_width("💻 CPU", 8), "💻 CPU  ");
+        assert_eq!(pad_to_width("中文", 5), "中文 ");
+        assert_eq!(pad_to_width("中文", 3), "中文");
+    }
+}
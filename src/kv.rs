@@ -0,0 +1,95 @@
+use crate::config::{Config, IconMode, RepeatLayout};
+use crate::sections::{self, RenderContext};
+use crate::system_info::SystemInfo;
+use crate::theme::Palette;
+
+/// Plain "label: value" lines without colors, for status bars and scripts.
+/// Labels carry no icon unless `--icons` asks for one.
+pub fn render(info: &SystemInfo, config: &Config) -> String {
+    let mut output = String::new();
+    for lines in section_texts(info, config) {
+        for text in &lines {
+            // Keep list items indented under their header; badges leave
+            // trailing padding
+            let text = if text.starts_with("  - ") {
+                text.trim_end()
+            } else {
+                text.trim()
+            };
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Like `render`, with each list folded onto its header line, so there is
+/// exactly one line per visible section, e.g.
+/// "Disks: /: 412.0GiB / 1000.0GiB (41%); /home: 1.3TiB / 2.0TiB (65%)"
+pub fn render_compact(info: &SystemInfo, config: &Config) -> String {
+    let mut output = String::new();
+    for lines in section_texts(info, config) {
+        let Some((header, items)) = lines.split_first() else {
+            continue;
+        };
+        output.push_str(header.trim());
+        if !items.is_empty() {
+            let items: Vec<&str> = items.iter().map(|item| compact_item(item)).collect();
+            output.push_str(": ");
+            output.push_str(&items.join("; "));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// A list item without its dash, and without its label when that only
+/// numbers the items ("CPU 1: ...")
+fn compact_item(item: &str) -> &str {
+    let item = item.trim().trim_start_matches("- ");
+    match item.split_once(": ") {
+        Some((label, value))
+            if label
+                .rsplit_once(' ')
+                .is_some_and(|(_, number)| number.parse::<usize>().is_ok()) =>
+        {
+            value
+        }
+        _ => item,
+    }
+}
+
+/// The text of each line of every visible section, grouped by section
+fn section_texts(info: &SystemInfo, config: &Config) -> Vec<Vec<String>> {
+    // Columns only make sense in the TUI
+    let config = Config {
+        repeat_layout: RepeatLayout::List,
+        ..config.clone()
+    };
+    let ctx = RenderContext {
+        info,
+        config: &config,
+        icons: config.icon_mode.unwrap_or(IconMode::Ascii),
+        cpu_history: None,
+        width: u16::MAX,
+        palette: Palette::default(),
+    };
+
+    sections::layout(&config)
+        .into_iter()
+        .map(sections::spec)
+        .filter(|spec| spec.is_available())
+        .map(|spec| {
+            (spec.render)(&ctx, spec)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect()
+                })
+                .collect::<Vec<String>>()
+        })
+        .filter(|lines| !lines.is_empty())
+        .collect()
+}
@@ -0,0 +1,25 @@
+//! System information collectors behind rsysfetch, usable without the TUI.
+//!
+//! ```no_run
+//! use rsysfetch::{Config, SystemInfo};
+//!
+//! let info = SystemInfo::collect(&Config::default())?;
+//! println!("{} {} on {}", info.os_name, info.os_version, info.hostname);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::error::Error;
+
+pub mod collector;
+pub mod config;
+pub mod fields;
+pub mod format;
+pub mod system_info;
+
+pub use collector::{Collector, MemoryUsage, MockCollector, SystemCollector};
+pub use config::Config;
+pub use system_info::{
+    BatteryInfo, CpuInfo, DiskInfo, DisplayInfo, GpuInfo, SystemInfo, TerminalCaps, Warning,
+};
+
+pub type AppResult<T> = Result<T, Box<dyn Error>>;
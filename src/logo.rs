@@ -1,8 +1,13 @@
+use std::fs;
+use std::path::Path;
+
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
+use crate::AppResult;
+
 const LOGO: &[&str] = &[
     "                                              ",
     "              ▄   ▓▄ ▄▓▓  ▓▓                  ",
@@ -22,14 +27,149 @@ const LOGO: &[&str] = &[
     "                                              ",
 ];
 
+const LOGO_ASCII: &[&str] = &[
+    "                        ",
+    "                        ",
+    "         _~^~^~_        ",
+    "     \\) /  o o  \\ (/    ",
+    "       '_   -   _'      ",
+    "       / '-----' \\      ",
+    "                        ",
+];
+
+const ARCH: &[&str] = &[
+    "                 ",
+    "        /\\       ",
+    "       /  \\      ",
+    "      /\\   \\     ",
+    "     /      \\    ",
+    "    /   ,,   \\   ",
+    "   /   |  |  -\\  ",
+    "  /_-''    ''-_\\ ",
+    "                 ",
+];
+
+const UBUNTU: &[&str] = &[
+    "               ",
+    "           _   ",
+    "       ---(_)  ",
+    "   _/  ---  \\  ",
+    "  (_) |   |    ",
+    "    \\  --- _/  ",
+    "       ---(_)  ",
+    "               ",
+];
+
+const FEDORA: &[&str] = &[
+    "                 ",
+    "        _____    ",
+    "       /   __)\\  ",
+    "       |  /  \\ \\ ",
+    "    ___|  |__/ / ",
+    "   / (_    _)_/  ",
+    "  / /  |  |      ",
+    "  \\ \\__/  |      ",
+    "   \\(_____/      ",
+    "                 ",
+];
+
+const DEBIAN: &[&str] = &[
+    "             ",
+    "    _____    ",
+    "   /  __ \\   ",
+    "  |  /    |  ",
+    "  |  \\___-   ",
+    "  -_         ",
+    "    --_      ",
+    "             ",
+];
+
+const MACOS: &[&str] = &[
+    "                  ",
+    "          .:'     ",
+    "      __ :'__     ",
+    "   .'`  `-'  ``.  ",
+    "  :          .-'  ",
+    "  :         :     ",
+    "   :         `-;  ",
+    "    `.__.-.__.'   ",
+    "                  ",
+];
+
+const WINDOWS: &[&str] = &[
+    "                     ",
+    "  ######## ########  ",
+    "  ######## ########  ",
+    "  ######## ########  ",
+    "                     ",
+    "  ######## ########  ",
+    "  ######## ########  ",
+    "  ######## ########  ",
+    "                     ",
+];
+
+const LOGO_PLACEHOLDER: &[&str] = &[
+    "                        ",
+    "        _______         ",
+    "       |       |        ",
+    "       |  >_   |        ",
+    "       |_______|        ",
+    "                        ",
+];
+
 pub fn get_logo() -> Vec<Line<'static>> {
+    build_logo(LOGO, Color::Red, "🦀 Copyright (C) 2025 Stepfen Shawn")
+}
+
+/// Logo of the given OS or distribution (an os-release `ID`, or "macos" and
+/// "windows"), falling back to the default logo
+pub fn get_logo_for(distro_id: &str) -> Vec<Line<'static>> {
+    distro_logo(distro_id).unwrap_or_else(get_logo)
+}
+
+/// Logo of the given OS or distribution, if there is one. The art is
+/// plain ASCII, so it suits every icon set.
+pub fn distro_logo(distro_id: &str) -> Option<Vec<Line<'static>>> {
+    let (art, color) = match distro_id {
+        "arch" => (ARCH, Color::LightCyan),
+        "ubuntu" => (UBUNTU, Color::LightRed),
+        "fedora" => (FEDORA, Color::LightBlue),
+        "debian" => (DEBIAN, Color::Red),
+        "macos" => (MACOS, Color::LightGreen),
+        "windows" => (WINDOWS, Color::LightBlue),
+        _ => return None,
+    };
+    Some(build_logo(art, color, "Copyright (C) 2025 Stepfen Shawn"))
+}
+
+/// Default logo with a Nerd Font Rust glyph in place of the crab emoji
+pub fn get_logo_nerd() -> Vec<Line<'static>> {
+    build_logo(
+        LOGO,
+        Color::Red,
+        "\u{e7a8} Copyright (C) 2025 Stepfen Shawn",
+    )
+}
+
+/// Pure-ASCII logo for consoles without unicode support
+pub fn get_logo_ascii() -> Vec<Line<'static>> {
+    build_logo(LOGO_ASCII, Color::Red, "Copyright (C) 2025 Stepfen Shawn")
+}
+
+/// Minimal generic logo used when no other logo is available
+pub fn get_placeholder_logo() -> Vec<Line<'static>> {
+    build_logo(
+        LOGO_PLACEHOLDER,
+        Color::Red,
+        "Copyright (C) 2025 Stepfen Shawn",
+    )
+}
+
+fn build_logo(art: &[&'static str], color: Color, copyright: &'static str) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
-    for line in LOGO {
-        lines.push(Line::from(Span::styled(
-            *line,
-            Style::default().fg(Color::Red),
-        )));
+    for line in art {
+        lines.push(Line::from(Span::styled(*line, Style::default().fg(color))));
     }
 
     lines.push(Line::from(""));
@@ -41,10 +181,153 @@ pub fn get_logo() -> Vec<Line<'static>> {
     )));
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "🦀 Copyright (C) 2025 Stepfen Shawn",
+        copyright,
         Style::default().fg(Color::Yellow),
     )));
     lines.push(Line::from(""));
 
     lines
 }
+
+/// Load ASCII or ANSI art from a file, keeping its SGR colors and attributes
+pub fn from_file(path: &Path) -> AppResult<Vec<Line<'static>>> {
+    let art = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read logo {}: {err}", path.display()))?;
+    Ok(parse_ansi(&art))
+}
+
+/// Split text into lines of styled spans. SGR sequences (`ESC [ ... m`)
+/// style the following text, carrying over line breaks as in a terminal;
+/// other escape sequences are dropped.
+fn parse_ansi(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut style = Style::default();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => {
+                if chars.next_if_eq(&'[').is_none() {
+                    // Two-character escape such as `ESC c`
+                    chars.next();
+                    continue;
+                }
+                let mut params = String::new();
+                let mut command = None;
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        command = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if command == Some('m') {
+                    if !current.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut current), style));
+                    }
+                    style = apply_sgr(style, &params);
+                }
+            }
+            '\n' => {
+                if !current.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut current), style));
+                }
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+            '\r' => {}
+            '\t' => current.push_str("    "),
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, style));
+    }
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Apply the `;`-separated parameters of an SGR sequence to a style
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    const COLORS: [Color; 8] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+    ];
+    const BRIGHT: [Color; 8] = [
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+
+    let mut codes = params
+        .split(';')
+        .map(|code| code.parse::<u8>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(COLORS[(code - 30) as usize]),
+            40..=47 => style.bg(COLORS[(code - 40) as usize]),
+            90..=97 => style.fg(BRIGHT[(code - 90) as usize]),
+            100..=107 => style.bg(BRIGHT[(code - 100) as usize]),
+            39 => style.fg(Color::Reset),
+            49 => style.bg(Color::Reset),
+            38 | 48 => {
+                let color = match codes.next() {
+                    Some(5) => codes.next().map(Color::Indexed),
+                    Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                        (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match (code, color) {
+                    (38, Some(color)) => style.fg(color),
+                    (_, Some(color)) => style.bg(color),
+                    (_, None) => style,
+                }
+            }
+            _ => style,
+        };
+    }
+    style
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_ascii(lines: &[Line]) -> bool {
+        lines
+            .iter()
+            .all(|line| line.spans.iter().all(|span| span.content.is_ascii()))
+    }
+
+    #[test]
+    fn ascii_logos_are_ascii() {
+        assert!(is_ascii(&get_logo_ascii()));
+        assert!(is_ascii(&get_placeholder_logo()));
+    }
+}
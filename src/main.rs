@@ -1,33 +1,141 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseEventKind,
+    },
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    style::Print,
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, SetTitle, disable_raw_mode, enable_raw_mode,
+    },
 };
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
 };
-use std::{error::Error, io};
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    time::{Duration, Instant},
+};
+
+// Data collection lives in the library; the modules below only present it
+use rsysfetch::{AppResult, config, fields, format, system_info};
 
 mod app;
+mod cli;
+mod demo;
+mod kv;
 mod logo;
-mod system_info;
+mod markdown;
+mod print;
+mod sections;
+mod snapshot;
+#[cfg(feature = "svg")]
+mod svg;
+mod theme;
 mod ui;
 
 use app::App;
+use clap::Parser;
+use cli::{Cli, Mode};
+use config::{Config, RepeatLayout};
+
+/// Width for `--print` when there is no terminal to measure
+const PRINT_WIDTH: u16 = 100;
 
-type AppResult<T> = Result<T, Box<dyn Error>>;
+/// Rows the info text moves per mouse wheel notch
+const WHEEL_ROWS: i32 = 3;
+
+/// How often watch mode refreshes CPU and memory figures
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+// xterm window-ops: push/pop the window and icon title on the title stack
+const SAVE_TITLE: &str = "\x1b[22;0t";
+const RESTORE_TITLE: &str = "\x1b[23;0t";
 
 fn main() -> AppResult<()> {
+    let cli = Cli::parse();
+    let mut config = Config::load(cli.profile.as_deref())?;
+    let mode = cli.apply(&mut config);
+    let unknown = sections::unknown_keys(&config);
+    config.load_warnings.extend(unknown);
+    config.count_processes = counts_processes(&config, &mode);
+
+    match mode {
+        Mode::FieldsJson => {
+            return write_output(&config, format!("{}\n", fields::to_json()?));
+        }
+        Mode::Kv => {
+            let info = app::collect(&config)?;
+            report_errors(&info, &config);
+            return write_output(&config, kv::render(&info, &config));
+        }
+        Mode::Compact => {
+            let info = app::collect(&config)?;
+            report_errors(&info, &config);
+            return write_output(&config, kv::render_compact(&info, &config));
+        }
+        Mode::Json => {
+            let info = app::collect(&config)?;
+            report_errors(&info, &config);
+            return write_output(&config, format!("{}\n", fields::info_to_json(&info)?));
+        }
+        Mode::Yaml => {
+            let info = app::collect(&config)?;
+            report_errors(&info, &config);
+            return write_output(&config, fields::info_to_yaml(&info)?);
+        }
+        Mode::Markdown => {
+            // Columns only make sense in the TUI
+            let config = Config {
+                repeat_layout: RepeatLayout::List,
+                ..config
+            };
+            let app = App::new(config)?;
+            report_errors(&app.system_info, &app.config);
+            return write_output(&app.config, markdown::render(&app));
+        }
+        Mode::Svg(path) => return export_svg(config, &path),
+        Mode::Print => {
+            let color = config.output.is_none() && theme::should_use_color();
+            let width = crossterm::terminal::size()
+                .ok()
+                .map(|(width, _)| width)
+                .filter(|&width| width > 0)
+                .unwrap_or(PRINT_WIDTH);
+            let app = App::new(config)?;
+            report_errors(&app.system_info, &app.config);
+            let text = print::render(&app, width, color);
+            return write_output(&app.config, text);
+        }
+        Mode::Tui => {}
+    }
+
+    // Collect before touching the terminal so errors print normally, and
+    // stay on the main screen once the TUI exits
+    let app = App::new(config)?;
+    report_errors(&app.system_info, &app.config);
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app = App::new()?;
+    let set_title = app.config.set_title && io::stdout().is_terminal();
+    if set_title {
+        execute!(
+            terminal.backend_mut(),
+            Print(SAVE_TITLE),
+            SetTitle(format!("sysfetch — {}", app.system_info.hostname))
+        )?;
+    }
+
     let res = run_app(&mut terminal, app);
 
+    if set_title {
+        execute!(terminal.backend_mut(), Print(RESTORE_TITLE))?;
+    }
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -43,17 +151,126 @@ fn main() -> AppResult<()> {
     Ok(())
 }
 
+/// The structured formats report every field; the others only need the
+/// processes counted when they show them
+fn counts_processes(config: &Config, mode: &Mode) -> bool {
+    matches!(mode, Mode::Json | Mode::Yaml)
+        || sections::layout(config).contains(&sections::Section::Processes)
+}
+
+/// With `--verbose`, list skipped configuration and the probes that
+/// failed on stderr
+fn report_errors(info: &system_info::SystemInfo, config: &Config) {
+    if config.verbose {
+        for error in config.load_warnings.iter().chain(&info.collection_errors) {
+            eprintln!("{error}");
+        }
+    }
+}
+
+/// Print the output, or write it to the `--output` file, creating its
+/// directory if needed
+fn write_output(config: &Config, text: String) -> AppResult<()> {
+    let Some(path) = &config.output else {
+        print!("{text}");
+        return Ok(());
+    };
+    let write = || {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)
+    };
+    write().map_err(|err| format!("cannot write {}: {err}", path.display()).into())
+}
+
+/// Render one frame at the current terminal size and write it as SVG
+#[cfg(feature = "svg")]
+fn export_svg(config: Config, path: &std::path::Path) -> AppResult<()> {
+    let (width, height) = crossterm::terminal::size().unwrap_or((120, 40));
+    let app = App::new(config)?;
+    report_errors(&app.system_info, &app.config);
+    let buffer = ui::render_to_buffer(&app, width, height)?;
+    std::fs::write(path, svg::from_buffer(&buffer))
+        .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(not(feature = "svg"))]
+fn export_svg(_config: Config, _path: &std::path::Path) -> AppResult<()> {
+    Err("--svg needs rsysfetch built with the `svg` feature".into())
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> AppResult<()> {
+    let mut last_tick = Instant::now();
+    let mut viewport = ui::Viewport::default();
     loop {
-        terminal.draw(|f| ui::draw(f, &app))?;
+        terminal.draw(|f| viewport = ui::draw(f, &app))?;
+
+        // Wake up for the first of the watch tick, the periodic refresh and
+        // the change markers expiring
+        let timeout = [
+            app.config
+                .watch
+                .then(|| WATCH_INTERVAL.saturating_sub(last_tick.elapsed())),
+            app.refresh_timeout(),
+            app.highlight_remaining(),
+            app.status_remaining(),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        if let Some(timeout) = timeout {
+            if !event::poll(timeout)? {
+                if app.config.watch && last_tick.elapsed() >= WATCH_INTERVAL {
+                    app.history_cap = ui::info_panel_width(terminal.size()?, &app.config) as usize;
+                    app.tick();
+                    last_tick = Instant::now();
+                }
+                app.update_refresh();
+                continue;
+            }
+        }
 
-        if let Event::Key(key) = event::read()? {
-            match key.code {
-                KeyCode::Char('q') | KeyCode::Esc => {
-                    app.should_quit = true;
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => app.scroll_by(-WHEEL_ROWS, viewport.max_scroll),
+                    MouseEventKind::ScrollDown => app.scroll_by(WHEEL_ROWS, viewport.max_scroll),
+                    _ => {}
                 }
-                _ => {}
+                continue;
+            }
+            _ => continue,
+        };
+        // Keep one row of context when paging
+        let page = viewport.height.saturating_sub(1).max(1) as i32;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.should_quit = true;
+            }
+            KeyCode::Char('i') => {
+                app.hide_ip = !app.hide_ip;
+            }
+            // Raw mode swallows SIGINT, so handle Ctrl-C as a key
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.should_quit = true;
+            }
+            KeyCode::Char('c') => {
+                app.show_cores = !app.show_cores;
+            }
+            KeyCode::Char('n') => {
+                app.show_interfaces = !app.show_interfaces;
             }
+            KeyCode::Char('y') => app.copy_to_clipboard(),
+            KeyCode::Up => app.scroll_by(-1, viewport.max_scroll),
+            KeyCode::Down => app.scroll_by(1, viewport.max_scroll),
+            KeyCode::PageUp => app.scroll_by(-page, viewport.max_scroll),
+            KeyCode::PageDown => app.scroll_by(page, viewport.max_scroll),
+            KeyCode::Home => app.scroll = 0,
+            KeyCode::End => app.scroll = viewport.max_scroll,
+            _ => {}
         }
 
         if app.should_quit {
@@ -62,3 +279,27 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> AppResult<()
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn processes_are_counted_only_when_shown() {
+        assert!(counts_processes(&Config::default(), &Mode::Tui));
+
+        let fast = Config {
+            fast: true,
+            ..Config::default()
+        };
+        assert!(!counts_processes(&fast, &Mode::Tui));
+        assert!(!counts_processes(&fast, &Mode::Kv));
+
+        let hidden = Config {
+            hide: vec!["processes".to_string()],
+            ..Config::default()
+        };
+        assert!(!counts_processes(&hidden, &Mode::Print));
+        assert!(counts_processes(&hidden, &Mode::Json));
+    }
+}
@@ -0,0 +1,74 @@
+use ratatui::text::Line;
+
+use crate::app::App;
+use crate::config::IconMode;
+use crate::ui;
+
+/// Characters with a meaning in Markdown or in table cells
+const SPECIAL: &[char] = &['\\', '`', '*', '_', '|', '<', '>', '[', ']'];
+
+/// Render the logo in a code block above a GitHub-flavored table of the
+/// info lines, for pasting into issue reports. Labels carry no icon unless
+/// `--icons` asks for one.
+pub fn render(app: &App) -> String {
+    let icons = app.config.icon_mode.unwrap_or(IconMode::Ascii);
+
+    let logo: Vec<String> = ui::logo_lines(app, icons).iter().map(plain).collect();
+    // A fence longer than any backtick run in the logo can't be closed by it
+    let longest_run = logo
+        .iter()
+        .flat_map(|line| line.split(|c| c != '`'))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+
+    let mut output = String::new();
+    if !logo.is_empty() {
+        output.push_str(&fence);
+        output.push('\n');
+        for line in &logo {
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output.push_str(&fence);
+        output.push_str("\n\n");
+    }
+
+    output.push_str("| Field | Value |\n| --- | --- |\n");
+    for line in ui::info_lines(app, icons, u16::MAX) {
+        let text = plain(&line);
+        let text = text.trim();
+        // Blank lines only separate groups
+        if text.is_empty() {
+            continue;
+        }
+        let (label, value) = match text.split_once(": ") {
+            // List items lose their dash, the table already separates them
+            Some((label, value)) => (escape(label.trim_start_matches("- ")), escape(value.trim())),
+            // List headers and badges
+            None => (format!("**{}**", escape(text)), String::new()),
+        };
+        output.push_str(&format!("| {label} | {value} |\n"));
+    }
+    output
+}
+
+fn plain(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Backslash-escape characters that would end a cell or start formatting
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if SPECIAL.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,111 @@
+use crossterm::style::{Attribute, Color as TermColor, ContentStyle, Stylize};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+
+use crate::app::App;
+use crate::format::{display_width, truncate_to_width};
+use crate::ui;
+
+/// Cells between the logo and the info column
+const GAP: usize = 3;
+
+/// Narrowest info column worth keeping the logo for
+const MIN_INFO_WIDTH: usize = 30;
+
+/// Render the logo beside the info lines as plain text for a one-shot
+/// print, with ANSI colors when `color` is set. Lines are cut to `width`
+/// and the logo is dropped when it would leave too little room.
+pub fn render(app: &App, width: u16, color: bool) -> String {
+    let width = width as usize;
+    let icons = ui::resolve_icon_mode(app);
+
+    let mut info = vec![Line::from(ui::title_spans(app, icons)), Line::default()];
+    info.extend(ui::info_lines(app, icons, width as u16));
+
+    let mut logo = ui::logo_lines(app, icons);
+    if app.palette.is_monochrome() {
+        strip_colors(&mut info);
+        strip_colors(&mut logo);
+    }
+    let art_width = logo.iter().map(Line::width).max().unwrap_or(0);
+    // A custom logo can be arbitrarily wide, cut it rather than drop it
+    let art_width = if app.custom_logo.is_some() {
+        art_width.min(width.saturating_sub(MIN_INFO_WIDTH + GAP))
+    } else {
+        art_width
+    };
+    let logo_width = art_width + GAP;
+    let logo = if width >= logo_width + MIN_INFO_WIDTH {
+        logo
+    } else {
+        Vec::new()
+    };
+    let logo_width = if logo.is_empty() { 0 } else { logo_width };
+    let info_width = width.saturating_sub(logo_width);
+
+    let mut output = String::new();
+    for row in 0..logo.len().max(info.len()) {
+        let mut line = String::new();
+        if let Some(logo_line) = logo.get(row) {
+            let used = write_line(&mut line, logo_line, art_width, color);
+            line.push_str(&" ".repeat(logo_width - used));
+        } else if row < info.len() {
+            line.push_str(&" ".repeat(logo_width));
+        }
+        if let Some(info_line) = info.get(row) {
+            write_line(&mut line, info_line, info_width, color);
+        }
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Drop the fixed colors of the logo and badges, keeping attributes
+fn strip_colors(lines: &mut [Line]) {
+    for span in lines.iter_mut().flat_map(|line| line.spans.iter_mut()) {
+        span.style.fg = None;
+        span.style.bg = None;
+    }
+}
+
+/// Append a line cut to `max_width` cells and return the cells written
+fn write_line(output: &mut String, line: &Line, max_width: usize, color: bool) -> usize {
+    let mut used = 0;
+    for span in &line.spans {
+        let text = truncate_to_width(&span.content, max_width - used);
+        used += display_width(&text);
+        if text.is_empty() {
+            continue;
+        }
+        if color {
+            output.push_str(&ansi_style(span.style).apply(text).to_string());
+        } else {
+            output.push_str(&text);
+        }
+        if used >= max_width {
+            break;
+        }
+    }
+    used
+}
+
+/// Translate a ratatui style into the equivalent terminal escape style
+fn ansi_style(style: Style) -> ContentStyle {
+    let color = |color: Option<Color>| color.filter(|&c| c != Color::Reset).map(TermColor::from);
+    let mut content = ContentStyle::new();
+    content.foreground_color = color(style.fg);
+    content.background_color = color(style.bg);
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            content = content.attribute(attribute);
+        }
+    }
+    content
+}
@@ -0,0 +1,1338 @@
+use std::collections::{HashMap, VecDeque};
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::config::{ByteUnits, Config, IconMode, RepeatLayout};
+use crate::format::{
+    display_width, format_bytes, format_count, format_frequency, format_percent,
+    format_temperature, format_timestamp, format_usage, sparkline, truncate_to_width,
+};
+use crate::system_info::{CpuInfo, SystemInfo};
+use crate::theme::Palette;
+
+const ALL: &[&str] = &["linux", "macos", "windows"];
+const LINUX: &[&str] = &["linux"];
+const UNIX: &[&str] = &["linux", "macos"];
+
+/// Labels up to this width are aligned into one column; wider ones
+/// (e.g. long mount points) are left as is
+pub const MAX_LABEL_WIDTH: usize = 16;
+
+/// Every line group that can appear in the info panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Os,
+    Kernel,
+    Host,
+    Machine,
+    MachineId,
+    User,
+    Uptime,
+    Boot,
+    Locale,
+    Timezone,
+    Packages,
+    Cpu,
+    CpuTemp,
+    Turbo,
+    Load,
+    Processes,
+    Gpu,
+    Display,
+    Disk,
+    LocalIp,
+    Net,
+    PublicIp,
+    Gateway,
+    Dns,
+    Vpn,
+    Shell,
+    Terminal,
+    Cwd,
+    Entropy,
+    Fds,
+    Sysctls,
+    Updates,
+    Memory,
+    Swap,
+    Battery,
+}
+
+/// Everything a section needs to render itself
+pub struct RenderContext<'a> {
+    pub info: &'a SystemInfo,
+    pub config: &'a Config,
+    pub icons: IconMode,
+    /// CPU usage history per model, present in watch mode
+    pub cpu_history: Option<&'a HashMap<String, VecDeque<f32>>>,
+    /// Usable width of the panel in cells
+    pub width: u16,
+    pub palette: Palette,
+}
+
+/// Registry entry describing how a section is labelled and rendered
+pub struct SectionSpec {
+    pub section: Section,
+    /// Key used in config tables such as `[icons]`
+    pub key: &'static str,
+    pub caption: &'static str,
+    pub emoji: &'static str,
+    pub glyph: &'static str,
+    pub default_visible: bool,
+    pub platforms: &'static [&'static str],
+    /// Sections in different groups are separated by a blank line
+    pub group: u8,
+    pub render: fn(&RenderContext, &SectionSpec) -> Vec<Line<'static>>,
+}
+
+impl SectionSpec {
+    /// Whether the section can show data on the current platform
+    pub fn is_available(&self) -> bool {
+        self.platforms.contains(&std::env::consts::OS)
+    }
+
+    /// Label for the section, including the icon when one applies
+    pub fn label(&self, ctx: &RenderContext) -> String {
+        icon_label(
+            ctx.config,
+            ctx.icons,
+            self.key,
+            self.emoji,
+            self.glyph,
+            self.caption,
+        )
+    }
+}
+
+/// Section registry in default display order
+pub const SECTIONS: &[SectionSpec] = &[
+    SectionSpec {
+        section: Section::Os,
+        key: "os",
+        caption: "OS",
+        emoji: "",
+        glyph: "",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            vec![field_line(
+                ctx,
+                spec.label(ctx),
+                format!("{} {}, {}", info.os_name, info.os_version, info.os_arch),
+            )]
+        },
+    },
+    SectionSpec {
+        section: Section::Kernel,
+        key: "kernel",
+        caption: "Kernel",
+        emoji: "",
+        glyph: "",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            vec![field_line(
+                ctx,
+                spec.label(ctx),
+                ctx.info.kernel_version.clone(),
+            )]
+        },
+    },
+    SectionSpec {
+        section: Section::Host,
+        key: "host",
+        caption: "Host",
+        emoji: "",
+        glyph: "",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            // "VM: KVM" on a hypervisor, "Desktop, Docker" in a container
+            let form = match (info.chassis_type.as_deref(), info.virtualization.as_deref()) {
+                (Some("VM"), Some(virt)) => Some(format!("VM: {virt}")),
+                (Some(chassis), Some(virt)) => Some(format!("{chassis}, {virt}")),
+                (Some(chassis), None) => Some(chassis.to_string()),
+                (None, virt) => virt.map(str::to_string),
+            };
+            let host = match form {
+                Some(form) => format!("{} ({form})", info.hostname),
+                None => info.hostname.clone(),
+            };
+            vec![field_line(ctx, spec.label(ctx), host)]
+        },
+    },
+    SectionSpec {
+        section: Section::Machine,
+        key: "machine",
+        caption: "Machine",
+        emoji: "🧰",
+        glyph: "\u{f0893}",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            let Some(machine) = &ctx.info.machine else {
+                return Vec::new();
+            };
+            let manufacturer = short_manufacturer(&machine.manufacturer);
+            // Some vendors repeat themselves in the model name
+            let mut value = if machine.product.starts_with(manufacturer) {
+                machine.product.clone()
+            } else {
+                format!("{manufacturer} {}", machine.product)
+            };
+            if let Some(bios) = &machine.bios_version {
+                value.push_str(&format!(" (BIOS {bios})"));
+            }
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::MachineId,
+        key: "machine-id",
+        caption: "Machine ID",
+        emoji: "",
+        glyph: "",
+        default_visible: false,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            ctx.info
+                .machine_id
+                .iter()
+                .map(|id| field_line(ctx, spec.label(ctx), id.clone()))
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::User,
+        key: "user",
+        caption: "User",
+        emoji: "",
+        glyph: "",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| vec![field_line(ctx, spec.label(ctx), ctx.info.username.clone())],
+    },
+    SectionSpec {
+        section: Section::Uptime,
+        key: "uptime",
+        caption: "Uptime",
+        emoji: "",
+        glyph: "",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| vec![field_line(ctx, spec.label(ctx), ctx.info.uptime.clone())],
+    },
+    SectionSpec {
+        section: Section::Boot,
+        key: "boot",
+        caption: "Boot",
+        emoji: "🕐",
+        glyph: "\u{f017}",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            vec![field_line(
+                ctx,
+                spec.label(ctx),
+                format_timestamp(ctx.info.boot_time),
+            )]
+        },
+    },
+    SectionSpec {
+        section: Section::Locale,
+        key: "locale",
+        caption: "Locale",
+        emoji: "🗣",
+        glyph: "\u{f1ab}",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            ctx.info
+                .locale
+                .iter()
+                .map(|locale| field_line(ctx, spec.label(ctx), locale.clone()))
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Timezone,
+        key: "timezone",
+        caption: "Timezone",
+        emoji: "🕐",
+        glyph: "\u{f0954}",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            ctx.info
+                .timezone
+                .iter()
+                .map(|timezone| field_line(ctx, spec.label(ctx), timezone.clone()))
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Packages,
+        key: "packages",
+        caption: "Packages",
+        emoji: "📦",
+        glyph: "\u{f487}",
+        default_visible: true,
+        platforms: ALL,
+        group: 0,
+        render: |ctx, spec| {
+            if ctx.info.packages.is_empty() {
+                return Vec::new();
+            }
+            let value = ctx
+                .info
+                .packages
+                .iter()
+                .map(|(manager, count)| {
+                    format!(
+                        "{} ({manager})",
+                        format_count(*count as u64, ctx.config.number_format)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::Cpu,
+        key: "cpu",
+        caption: "CPUs",
+        emoji: "🔥",
+        glyph: "\u{f4bc}",
+        default_visible: true,
+        platforms: ALL,
+        group: 1,
+        render: |ctx, spec| {
+            let items = ctx.info.cpus.iter().map(|cpu| {
+                let format = ctx.config.number_format;
+                let mut cores = match cpu.physical_cores {
+                    Some(physical) if physical != cpu.cores => format!(
+                        "{} cores, {} threads",
+                        format_count(physical as u64, format),
+                        format_count(cpu.cores as u64, format)
+                    ),
+                    _ => format!("{} cores", format_count(cpu.cores as u64, format)),
+                };
+                // The online count is system-wide, so only pair it with a single model
+                if let (Some(online), [_]) = (ctx.info.cores_online, ctx.info.cpus.as_slice()) {
+                    if online != cpu.cores {
+                        cores.push_str(&format!(
+                            " ({} online)",
+                            format_count(online as u64, format)
+                        ));
+                    }
+                }
+                let mut value = format!(
+                    "{} ({}) @ {} ({})",
+                    cpu.name,
+                    cores,
+                    cpu_frequency(ctx, cpu),
+                    format_percent(cpu.usage as f64, ctx.config.percent_precision)
+                );
+                if let Some(watts) = cpu.power_draw {
+                    value.push_str(&format!(" ({watts:.0}W)"));
+                }
+
+                // Show as much recent history as fits after the value
+                if let Some(history) = ctx.cpu_history.and_then(|h| h.get(&cpu.model)) {
+                    let room = (ctx.width as usize)
+                        .saturating_sub(MAX_LABEL_WIDTH + display_width(&value) + 1);
+                    let samples = history.len().min(room);
+                    if samples > 0 {
+                        let recent = history.iter().skip(history.len() - samples).copied();
+                        value.push(' ');
+                        value.push_str(&sparkline(recent, ctx.icons == IconMode::Ascii));
+                    }
+                }
+                value
+            });
+            let mut lines = list_lines(ctx, spec.label(ctx), "CPU", items);
+            if ctx.info.thermal_throttling == Some(true) {
+                if let Some(header) = lines.first_mut() {
+                    header.spans.push(throttling_badge(ctx));
+                }
+            }
+            lines
+        },
+    },
+    SectionSpec {
+        section: Section::CpuTemp,
+        key: "cpu-temp",
+        caption: "CPU Temp",
+        emoji: "🌡️",
+        glyph: "\u{f2c9}",
+        default_visible: true,
+        platforms: UNIX,
+        group: 1,
+        render: |ctx, spec| {
+            ctx.info
+                .cpu_temp
+                .map(|celsius| {
+                    field_line(
+                        ctx,
+                        spec.label(ctx),
+                        format_temperature(celsius, ctx.config.temperature_unit),
+                    )
+                })
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Turbo,
+        key: "turbo",
+        caption: "Turbo",
+        emoji: "🚀",
+        glyph: "\u{f0e7}",
+        default_visible: false,
+        platforms: LINUX,
+        group: 1,
+        render: |ctx, spec| {
+            ctx.info
+                .turbo_enabled
+                .map(|on| {
+                    field_line(
+                        ctx,
+                        spec.label(ctx),
+                        if on { "on" } else { "off" }.to_string(),
+                    )
+                })
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Load,
+        key: "load",
+        caption: "Load",
+        emoji: "⚖️",
+        glyph: "\u{f0e4}",
+        default_visible: true,
+        platforms: UNIX,
+        group: 1,
+        render: |ctx, spec| {
+            ctx.info
+                .load_average
+                .map(|(one, five, fifteen)| {
+                    field_line(
+                        ctx,
+                        spec.label(ctx),
+                        format!("{one:.2} {five:.2} {fifteen:.2}"),
+                    )
+                })
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Processes,
+        key: "processes",
+        caption: "Processes",
+        emoji: "🧮",
+        glyph: "\u{f0ae}",
+        default_visible: true,
+        platforms: ALL,
+        group: 1,
+        render: |ctx, spec| {
+            let Some(count) = ctx.info.process_count else {
+                return Vec::new();
+            };
+            let format = ctx.config.number_format;
+            let mut value = format_count(count as u64, format);
+            if let Some(running) = ctx.info.running_process_count {
+                value.push_str(&format!(
+                    " ({} running)",
+                    format_count(running as u64, format)
+                ));
+            }
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::Gpu,
+        key: "gpu",
+        caption: "GPUs",
+        emoji: "🎮",
+        glyph: "\u{f08ae}",
+        default_visible: true,
+        platforms: ALL,
+        group: 1,
+        render: |ctx, spec| {
+            let items = ctx.info.gpus.iter().map(|gpu| {
+                let mut details = Vec::new();
+                let binary = binary_units(ctx);
+                match (gpu.vram_used, gpu.vram_total) {
+                    (Some(used), Some(total)) => details.push(format!(
+                        "{} / {}",
+                        format_bytes(used, binary),
+                        format_bytes(total, binary)
+                    )),
+                    (None, Some(total)) => details.push(format_bytes(total, binary)),
+                    _ => {}
+                }
+                if let Some(utilization) = gpu.utilization {
+                    details.push(format_percent(
+                        utilization as f64,
+                        ctx.config.percent_precision,
+                    ));
+                }
+                if let Some(metal) = &gpu.metal {
+                    details.push(metal.clone());
+                }
+
+                let mut value = gpu.name.clone();
+                if let Some(driver) = &gpu.driver_version {
+                    value.push_str(&format!(" (driver {driver})"));
+                }
+                if !details.is_empty() {
+                    value.push_str(&format!(" ({})", details.join(", ")));
+                }
+                if let Some(watts) = gpu.power_draw {
+                    value.push_str(&format!(" ({watts:.0}W)"));
+                }
+                value
+            });
+            list_lines(ctx, spec.label(ctx), "GPU", items)
+        },
+    },
+    SectionSpec {
+        section: Section::Display,
+        key: "display",
+        caption: "Display",
+        emoji: "🖥️",
+        glyph: "\u{f0379}",
+        default_visible: true,
+        platforms: ALL,
+        group: 1,
+        render: |ctx, spec| {
+            ctx.info
+                .displays
+                .iter()
+                .map(|display| {
+                    let mut value = format!("{}x{}", display.width, display.height);
+                    if let Some(refresh_hz) = display.refresh_hz {
+                        value.push_str(&format!(" @ {}Hz", refresh_hz.round()));
+                    }
+                    if let Some(scale) = display.scale_factor.filter(|&scale| scale != 1.0) {
+                        value.push_str(&format!(" @ {scale}x"));
+                    }
+                    field_line(ctx, spec.label(ctx), value)
+                })
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Disk,
+        key: "disk",
+        caption: "Disks",
+        emoji: "💽",
+        glyph: "\u{f02ca}",
+        default_visible: true,
+        platforms: ALL,
+        group: 1,
+        render: |ctx, spec| {
+            let items = ctx.info.disks.iter().map(|disk| {
+                let usage = format_usage(
+                    disk.used,
+                    disk.total,
+                    binary_units(ctx),
+                    ctx.config.memory_display,
+                    ctx.config.percent_precision,
+                );
+                (disk.mount_point.clone(), usage)
+            });
+            item_lines(ctx, spec.label(ctx), items)
+        },
+    },
+    SectionSpec {
+        section: Section::LocalIp,
+        key: "ip",
+        caption: "Local IP",
+        emoji: "🌐",
+        glyph: "\u{f0ac}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            let interface = info.interfaces.iter().find(|interface| {
+                interface.ipv4.contains(&info.local_ip) || interface.ipv6.contains(&info.local_ip)
+            });
+            let ip = match interface {
+                Some(interface) => format!("{} ({})", info.local_ip, interface.name),
+                None => info.local_ip.clone(),
+            };
+            vec![field_line(ctx, spec.label(ctx), ip)]
+        },
+    },
+    SectionSpec {
+        section: Section::Net,
+        key: "net",
+        caption: "Net",
+        emoji: "🌐",
+        glyph: "\u{f04e1}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let Some(interface) = ctx.info.interfaces.first() else {
+                return Vec::new();
+            };
+            // Rates only exist while sampling; a one-shot run would
+            // always show "--"
+            let live = ctx.config.watch || ctx.config.refresh > 0;
+            if !live && interface.rx_rate.is_none() {
+                return Vec::new();
+            }
+            let (down, up) = if ctx.icons == IconMode::Ascii {
+                ("rx", "tx")
+            } else {
+                ("↓", "↑")
+            };
+            let rate = |rate: Option<u64>| match rate {
+                Some(rate) => format!("{}/s", format_bytes(rate, binary_units(ctx))),
+                None => "--".to_string(),
+            };
+            let value = format!(
+                "{down} {} {up} {}",
+                rate(interface.rx_rate),
+                rate(interface.tx_rate)
+            );
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::PublicIp,
+        key: "public-ip",
+        caption: "Public IP",
+        emoji: "🌍",
+        glyph: "\u{f01e7}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            // Only when asked for, so sample data doesn't suggest a lookup happened
+            ctx.info
+                .public_ip
+                .clone()
+                .filter(|_| ctx.config.public_ip)
+                .map(|ip| field_line(ctx, spec.label(ctx), ip))
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Gateway,
+        key: "gateway",
+        caption: "Gateway",
+        emoji: "🚪",
+        glyph: "\u{f0317}",
+        default_visible: false,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let gateway = ctx.info.gateway.as_deref().unwrap_or("Unknown");
+            vec![field_line(ctx, spec.label(ctx), gateway.to_string())]
+        },
+    },
+    SectionSpec {
+        section: Section::Dns,
+        key: "dns",
+        caption: "DNS",
+        emoji: "🔎",
+        glyph: "\u{f0349}",
+        default_visible: false,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let servers = if ctx.info.dns_servers.is_empty() {
+                "Unknown".to_string()
+            } else {
+                ctx.info.dns_servers.join(", ")
+            };
+            vec![field_line(ctx, spec.label(ctx), servers)]
+        },
+    },
+    SectionSpec {
+        section: Section::Vpn,
+        key: "vpn",
+        caption: "VPN",
+        emoji: "🔐",
+        glyph: "\u{f023}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            if !ctx.info.vpn_active {
+                return Vec::new();
+            }
+            let value = match &ctx.info.vpn_interface {
+                Some(name) => format!("active ({name})"),
+                None => "active".to_string(),
+            };
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::Shell,
+        key: "shell",
+        caption: "Shell",
+        emoji: "🐚",
+        glyph: "\u{f489}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            let shell = match &info.shell_version {
+                Some(version) => format!("{} {version}", info.shell),
+                None => info.shell.clone(),
+            };
+            vec![field_line(ctx, spec.label(ctx), shell)]
+        },
+    },
+    SectionSpec {
+        section: Section::Terminal,
+        key: "terminal",
+        caption: "Terminal",
+        emoji: "📟",
+        glyph: "\u{e795}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| vec![field_line(ctx, spec.label(ctx), ctx.info.terminal.clone())],
+    },
+    SectionSpec {
+        section: Section::Cwd,
+        key: "cwd",
+        caption: "CWD",
+        emoji: "📁",
+        glyph: "\u{f07b}",
+        default_visible: false,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            ctx.info
+                .cwd
+                .iter()
+                .map(|cwd| field_line(ctx, spec.label(ctx), cwd.clone()))
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Entropy,
+        key: "entropy",
+        caption: "Entropy",
+        emoji: "🎲",
+        glyph: "\u{f01ba}",
+        default_visible: false,
+        platforms: LINUX,
+        group: 2,
+        render: |ctx, spec| {
+            ctx.info
+                .entropy_available
+                .map(|entropy| field_line(ctx, spec.label(ctx), entropy.to_string()))
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Fds,
+        key: "fds",
+        caption: "FDs",
+        emoji: "📂",
+        glyph: "\u{f07c}",
+        default_visible: false,
+        platforms: LINUX,
+        group: 2,
+        render: |ctx, spec| {
+            let format = ctx.config.number_format;
+            ctx.info
+                .open_fds
+                .map(|open| {
+                    let mut value = format_count(open as u64, format);
+                    if let Some(limit) = ctx.info.fd_limit {
+                        value.push_str(&format!(" (ulimit {})", format_count(limit, format)));
+                    }
+                    field_line(ctx, spec.label(ctx), value)
+                })
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Sysctls,
+        key: "sysctls",
+        caption: "Sysctls",
+        emoji: "🔧",
+        glyph: "\u{f0ad}",
+        default_visible: true,
+        platforms: LINUX,
+        group: 2,
+        render: |ctx, spec| {
+            let items = ctx.info.sysctls.iter().cloned();
+            item_lines(ctx, spec.label(ctx), items)
+        },
+    },
+    SectionSpec {
+        section: Section::Updates,
+        key: "updates",
+        caption: "Updates",
+        emoji: "⬆️",
+        glyph: "\u{f06b0}",
+        default_visible: true,
+        platforms: LINUX,
+        group: 2,
+        render: |ctx, spec| {
+            ctx.info
+                .updates_available
+                .map(|updates| {
+                    field_line(
+                        ctx,
+                        spec.label(ctx),
+                        format_count(updates as u64, ctx.config.number_format),
+                    )
+                })
+                .into_iter()
+                .collect()
+        },
+    },
+    SectionSpec {
+        section: Section::Memory,
+        key: "memory",
+        caption: "Memory",
+        emoji: "💾",
+        glyph: "\u{f035b}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            vec![field_line(
+                ctx,
+                spec.label(ctx),
+                format_usage(
+                    info.memory_used,
+                    info.memory_total,
+                    binary_units(ctx),
+                    ctx.config.memory_display,
+                    ctx.config.percent_precision,
+                ),
+            )]
+        },
+    },
+    SectionSpec {
+        section: Section::Swap,
+        key: "swap",
+        caption: "Swap",
+        emoji: "💾",
+        glyph: "\u{f0c7}",
+        default_visible: true,
+        platforms: ALL,
+        group: 2,
+        render: |ctx, spec| {
+            let info = ctx.info;
+            // No swap configured, or dynamic swap reporting nothing yet (macOS)
+            if info.swap_total == 0 {
+                return vec![field_line(ctx, spec.label(ctx), "disabled".to_string())];
+            }
+            let mut value = format_usage(
+                info.swap_used,
+                info.swap_total,
+                binary_units(ctx),
+                ctx.config.memory_display,
+                ctx.config.percent_precision,
+            );
+            if let Some(ratio) = info.zram_ratio {
+                value.push_str(&format!(" (zram {ratio:.1}:1)"));
+            }
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+    SectionSpec {
+        section: Section::Battery,
+        key: "battery",
+        caption: "Battery",
+        emoji: "🔋",
+        glyph: "\u{f0079}",
+        default_visible: true,
+        platforms: UNIX,
+        group: 2,
+        render: |ctx, spec| {
+            let Some(battery) = &ctx.info.battery else {
+                return Vec::new();
+            };
+            let mut details = Vec::new();
+            if battery.charging {
+                details.push("charging".to_string());
+            }
+            if let Some(time) = battery.time_remaining_text() {
+                details.push(if battery.charging {
+                    format!("{time} to full")
+                } else {
+                    format!("{time} left")
+                });
+            }
+            let mut value = format!("{}%", battery.percentage);
+            if !details.is_empty() {
+                value.push_str(&format!(" ({})", details.join(", ")));
+            }
+            vec![field_line(ctx, spec.label(ctx), value)]
+        },
+    },
+];
+
+/// Current clock, followed by the base and max clocks when they differ at
+/// the shown precision
+fn cpu_frequency(ctx: &RenderContext, cpu: &CpuInfo) -> String {
+    let precision = ctx.config.freq_precision;
+    let format = |mhz| format_frequency(mhz, precision);
+    // Some VMs report no current clock at all
+    let current = (cpu.frequency_current > 0).then(|| format(cpu.frequency_current));
+    let base = cpu
+        .frequency_base
+        .map(format)
+        .filter(|base| Some(base) != current.as_ref());
+    let max = cpu
+        .frequency_max
+        .map(format)
+        .filter(|max| Some(max) != current.as_ref() && Some(max) != base.as_ref());
+
+    let mut parts: Vec<String> = current.into_iter().collect();
+    parts.extend(base.map(|base| format!("{base} base")));
+    parts.extend(max.map(|max| format!("{max} max")));
+    if parts.is_empty() {
+        "Unknown".to_string()
+    } else {
+        parts.join(" / ")
+    }
+}
+
+/// Manufacturer without its legal suffix, e.g. "Dell" for "Dell Inc."
+fn short_manufacturer(manufacturer: &str) -> &str {
+    const SUFFIXES: [&str; 6] = [
+        " COMPUTER INC.",
+        " Computer Inc.",
+        " Inc.",
+        " Corporation",
+        " Co., Ltd.",
+        " GmbH",
+    ];
+    SUFFIXES
+        .iter()
+        .find_map(|suffix| manufacturer.strip_suffix(suffix))
+        .unwrap_or(manufacturer)
+}
+
+/// Whether byte sizes use powers of 1024
+fn binary_units(ctx: &RenderContext) -> bool {
+    ctx.config.byte_units == ByteUnits::Binary
+}
+
+/// Warning badge appended to the CPU header while the CPU is throttled
+fn throttling_badge(ctx: &RenderContext) -> Span<'static> {
+    let text = match ctx.icons {
+        IconMode::Emoji => " 🔥 Throttling! ",
+        IconMode::Nerd => " \u{f06d} Throttling! ",
+        IconMode::Ascii => " Throttling! ",
+    };
+    Span::styled(
+        text,
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Sections collected by the `fast` preset
+const FAST_SECTIONS: &[Section] = &[
+    Section::Os,
+    Section::Kernel,
+    Section::Uptime,
+    Section::Memory,
+    Section::Swap,
+];
+
+/// The sections listed in `fields` in that order, otherwise those shown by
+/// default plus those enabled through `show`; minus those in `hide`
+pub fn layout(config: &Config) -> Vec<Section> {
+    let mut specs: Vec<&SectionSpec> = match &config.fields {
+        Some(keys) => keys
+            .iter()
+            .filter_map(|key| SECTIONS.iter().find(|spec| spec.key == key))
+            .collect(),
+        None => SECTIONS
+            .iter()
+            .filter(|spec| spec.default_visible || config.show.iter().any(|key| key == spec.key))
+            .collect(),
+    };
+    // A key listed twice keeps its first position
+    let mut seen = Vec::new();
+    specs.retain(|spec| {
+        let first = !seen.contains(&spec.section);
+        seen.push(spec.section);
+        first
+    });
+
+    specs
+        .into_iter()
+        .filter(|spec| !config.fast || FAST_SECTIONS.contains(&spec.section))
+        .filter(|spec| !config.hide.iter().any(|key| key == spec.key))
+        .map(|spec| spec.section)
+        .collect()
+}
+
+/// A warning for every name in `fields`, `show` and `hide` that isn't a
+/// section key; those names are ignored
+pub fn unknown_keys(config: &Config) -> Vec<String> {
+    let lists = [
+        ("fields", config.fields.as_deref().unwrap_or_default()),
+        ("show", config.show.as_slice()),
+        ("hide", config.hide.as_slice()),
+    ];
+    lists
+        .into_iter()
+        .flat_map(|(option, keys)| {
+            keys.iter()
+                .filter(|key| !SECTIONS.iter().any(|spec| spec.key == key.as_str()))
+                .map(move |key| format!("unknown section '{key}' in {option}"))
+        })
+        .collect()
+}
+
+/// Look up the registry entry for a section
+pub fn spec(section: Section) -> &'static SectionSpec {
+    SECTIONS
+        .iter()
+        .find(|spec| spec.section == section)
+        .expect("every section is registered")
+}
+
+/// Label for the panel title
+pub fn title_label(config: &Config, icons: IconMode) -> String {
+    icon_label(config, icons, "title", "🖥️", "\u{f0379}", "Environments")
+}
+
+/// Build a label from the user's icon override or the default icon.
+/// In ASCII mode only ASCII icons are kept.
+fn icon_label(
+    config: &Config,
+    icons: IconMode,
+    key: &str,
+    emoji: &str,
+    glyph: &str,
+    caption: &str,
+) -> String {
+    let default_icon = match icons {
+        IconMode::Emoji => emoji,
+        IconMode::Nerd => glyph,
+        IconMode::Ascii => "",
+    };
+    let icon = config.icon(key).unwrap_or(default_icon);
+
+    if icon.is_empty() || (icons == IconMode::Ascii && !icon.is_ascii()) {
+        format!("  {caption}")
+    } else {
+        format!(" {icon} {caption}")
+    }
+}
+
+fn label_style(ctx: &RenderContext) -> Style {
+    Style::default()
+        .fg(ctx.palette.label)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn item_style(ctx: &RenderContext) -> Style {
+    Style::default()
+        .fg(ctx.palette.item)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn value_style(ctx: &RenderContext) -> Style {
+    Style::default().fg(ctx.palette.value)
+}
+
+/// A single "label: value" line
+fn field_line(ctx: &RenderContext, label: String, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!("{label}: "), label_style(ctx)),
+        Span::styled(value, value_style(ctx)),
+    ])
+}
+
+/// A header followed by numbered items, or nothing when there are no items
+fn list_lines(
+    ctx: &RenderContext,
+    header: String,
+    item: &str,
+    values: impl Iterator<Item = String>,
+) -> Vec<Line<'static>> {
+    let items = values
+        .enumerate()
+        .map(|(i, value)| (format!("{} {}", item, i + 1), value));
+    item_lines(ctx, header, items)
+}
+
+/// A header followed by labelled items, one per line or packed into columns
+/// depending on `repeat-layout`, or nothing when there are no items
+fn item_lines(
+    ctx: &RenderContext,
+    header: String,
+    items: impl Iterator<Item = (String, String)>,
+) -> Vec<Line<'static>> {
+    let items: Vec<_> = items.collect();
+    if items.is_empty() {
+        return Vec::new();
+    }
+
+    let mut lines = vec![Line::from(Span::styled(header, label_style(ctx)))];
+    match ctx.config.repeat_layout {
+        RepeatLayout::List => {
+            lines.extend(items.into_iter().map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(format!("  - {label}: "), item_style(ctx)),
+                    Span::styled(value, value_style(ctx)),
+                ])
+            }));
+        }
+        RepeatLayout::Grid => lines.extend(grid_lines(ctx, items)),
+    }
+    lines
+}
+
+/// Lay items out in up to three equal columns sized to the panel width
+fn grid_lines(ctx: &RenderContext, items: Vec<(String, String)>) -> Vec<Line<'static>> {
+    const INDENT: &str = "  ";
+    const MIN_CELL_WIDTH: u16 = 24;
+
+    let inner = ctx.width.saturating_sub(INDENT.len() as u16);
+    let columns = (inner / MIN_CELL_WIDTH).clamp(1, 3).min(items.len() as u16);
+    let cells = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns as usize])
+        .split(Rect::new(0, 0, inner, 1));
+
+    items
+        .chunks(columns as usize)
+        .map(|row| {
+            let mut spans = vec![Span::raw(INDENT)];
+            for (i, (label, value)) in row.iter().enumerate() {
+                // Keep a one-cell gap between columns, and leave the last
+                // cell of a row unpadded so short rows end cleanly
+                let width = (cells[i].width as usize).saturating_sub(1);
+                let label = truncate_to_width(&format!("{label}: "), width);
+                let value = truncate_to_width(value, width - display_width(&label));
+                let used = display_width(&label) + display_width(&value);
+                spans.push(Span::styled(label, item_style(ctx)));
+                spans.push(Span::raw(value));
+                if i + 1 < row.len() {
+                    spans.push(Span::raw(" ".repeat(cells[i].width as usize - used)));
+                }
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(info: &'a SystemInfo, config: &'a Config) -> RenderContext<'a> {
+        RenderContext {
+            info,
+            config,
+            icons: IconMode::Ascii,
+            cpu_history: None,
+            width: u16::MAX,
+            palette: Palette::default(),
+        }
+    }
+
+    #[test]
+    fn cpu_frequency_lists_the_clocks_that_are_known() {
+        let info = SystemInfo::sample();
+        let config = Config::default();
+        let ctx = context(&info, &config);
+        let frequency = |current, base, max| {
+            let cpu = CpuInfo {
+                frequency_current: current,
+                frequency_base: base,
+                frequency_max: max,
+                ..info.cpus[0].clone()
+            };
+            cpu_frequency(&ctx, &cpu)
+        };
+
+        // Current only
+        assert_eq!(frequency(3400, None, None), "3.40GHz");
+        // Base only
+        assert_eq!(frequency(0, Some(4200), None), "4.20GHz base");
+        // Both, plus the max
+        assert_eq!(frequency(3400, Some(4200), None), "3.40GHz / 4.20GHz base");
+        assert_eq!(
+            frequency(3400, Some(4200), Some(5000)),
+            "3.40GHz / 4.20GHz base / 5.00GHz max"
+        );
+        // Neither
+        assert_eq!(frequency(0, None, None), "Unknown");
+    }
+
+    #[test]
+    fn cpu_frequency_drops_clocks_equal_at_the_shown_precision() {
+        let info = SystemInfo::sample();
+        let config = Config {
+            freq_precision: 1,
+            ..Config::default()
+        };
+        let ctx = context(&info, &config);
+        let cpu = CpuInfo {
+            frequency_current: 4198,
+            frequency_base: Some(4200),
+            frequency_max: Some(4203),
+            ..info.cpus[0].clone()
+        };
+        assert_eq!(cpu_frequency(&ctx, &cpu), "4.2GHz");
+    }
+
+    #[test]
+    fn icon_label_prefers_a_valid_override() {
+        let config = Config {
+            icons: HashMap::from([
+                ("cpu".to_string(), "🔥".to_string()),
+                ("gpu".to_string(), "graphics card".to_string()),
+            ]),
+            ..Config::default()
+        };
+        let label =
+            |icons, key, caption| icon_label(&config, icons, key, "💻", "\u{f4bc}", caption);
+
+        assert_eq!(label(IconMode::Emoji, "cpu", "CPU"), " 🔥 CPU");
+        assert_eq!(label(IconMode::Nerd, "cpu", "CPU"), " 🔥 CPU");
+        // The rejected override falls back to the default icon
+        assert_eq!(label(IconMode::Emoji, "gpu", "GPU"), " 💻 GPU");
+        assert_eq!(label(IconMode::Nerd, "gpu", "GPU"), " \u{f4bc} GPU");
+        assert_eq!(label(IconMode::Emoji, "disk", "Disk"), " 💻 Disk");
+        // ASCII mode drops icons it can't show
+        assert_eq!(label(IconMode::Ascii, "cpu", "CPU"), "  CPU");
+        assert_eq!(label(IconMode::Ascii, "gpu", "GPU"), "  GPU");
+    }
+
+    fn keys(list: &[&str]) -> Vec<String> {
+        list.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn section_keys_and_sections_are_unique() {
+        for (i, entry) in SECTIONS.iter().enumerate() {
+            for other in &SECTIONS[i + 1..] {
+                assert_ne!(entry.key, other.key);
+                assert_ne!(entry.section, other.section, "{}", entry.key);
+            }
+            assert_eq!(spec(entry.section).key, entry.key);
+        }
+    }
+
+    #[test]
+    fn layout_follows_fields_in_order() {
+        let config = Config {
+            fields: Some(keys(&["memory", "os", "cwd", "os", "bogus"])),
+            // Ignored next to `fields`
+            show: keys(&["dns"]),
+            ..Config::default()
+        };
+        // Hidden-by-default sections can be listed; repeats keep their first place
+        assert_eq!(
+            layout(&config),
+            [Section::Memory, Section::Os, Section::Cwd]
+        );
+    }
+
+    #[test]
+    fn layout_adds_shown_and_drops_hidden_sections() {
+        let defaults = layout(&Config::default());
+        assert!(defaults.contains(&Section::Os));
+        assert!(!defaults.contains(&Section::Dns));
+
+        let config = Config {
+            show: keys(&["dns"]),
+            hide: keys(&["os"]),
+            ..Config::default()
+        };
+        let sections = layout(&config);
+        assert!(sections.contains(&Section::Dns));
+        assert!(!sections.contains(&Section::Os));
+        // `show` keeps the registry order
+        let position = |section| sections.iter().position(|&s| s == section);
+        assert!(position(Section::Gateway).is_none());
+        assert!(position(Section::Dns) > position(Section::LocalIp));
+
+        let config = Config {
+            fields: Some(keys(&["os", "cpu"])),
+            hide: keys(&["cpu"]),
+            ..Config::default()
+        };
+        assert_eq!(layout(&config), [Section::Os]);
+    }
+
+    #[test]
+    fn fast_layout_matches_the_documented_fields() {
+        let config = Config {
+            fast: true,
+            ..Config::default()
+        };
+        // README, `--fast` and `Config::fast` all list these
+        assert_eq!(
+            layout(&config),
+            [
+                Section::Os,
+                Section::Kernel,
+                Section::Uptime,
+                Section::Memory,
+                Section::Swap,
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_per_list() {
+        let config = Config {
+            fields: Some(keys(&["os", "colours"])),
+            show: keys(&["dns", "gpus"]),
+            hide: keys(&["kernal"]),
+            ..Config::default()
+        };
+        assert_eq!(
+            unknown_keys(&config),
+            [
+                "unknown section 'colours' in fields",
+                "unknown section 'gpus' in show",
+                "unknown section 'kernal' in hide",
+            ]
+        );
+        assert!(unknown_keys(&Config::default()).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::system_info::SystemInfo;
+
+/// Slow-changing values from the previous launch, keyed by section, used to
+/// point out what changed since then (a kernel update, a new IP address)
+type Snapshot = BTreeMap<String, String>;
+
+/// Compare against the snapshot saved by the previous launch, replace it
+/// with the current values and return the keys of the sections that changed.
+/// A missing or unreadable snapshot counts as "nothing changed".
+pub fn changed_sections(info: &SystemInfo) -> Vec<String> {
+    let current = stable_values(info);
+    let Some(path) = snapshot_path() else {
+        return Vec::new();
+    };
+
+    let previous: Option<Snapshot> = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    // Best effort: failing to save only loses the next comparison
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Ok(content) = serde_json::to_string_pretty(&current) {
+        let _ = fs::write(&path, content);
+    }
+
+    let Some(previous) = previous else {
+        return Vec::new();
+    };
+    current
+        .into_iter()
+        .filter(|(key, value)| previous.get(key).is_some_and(|old| old != value))
+        .map(|(key, _)| key)
+        .collect()
+}
+
+/// Values worth comparing between launches; usage figures and uptime change
+/// every run and would drown out the interesting differences
+fn stable_values(info: &SystemInfo) -> Snapshot {
+    let join = |values: Vec<String>| values.join(", ");
+    let displays = info
+        .displays
+        .iter()
+        .map(|display| format!("{}x{}", display.width, display.height))
+        .collect();
+
+    [
+        ("os", format!("{} {}", info.os_name, info.os_version)),
+        ("kernel", info.kernel_version.clone()),
+        ("host", info.hostname.clone()),
+        (
+            "gpu",
+            join(info.gpus.iter().map(|gpu| gpu.name.clone()).collect()),
+        ),
+        ("display", join(displays)),
+        ("ip", info.local_ip.clone()),
+        ("gateway", info.gateway.clone().unwrap_or_default()),
+        ("dns", join(info.dns_servers.clone())),
+        ("vpn", info.vpn_interface.clone().unwrap_or_default()),
+        ("shell", info.shell.clone()),
+        ("terminal", info.terminal.clone()),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value))
+    .collect()
+}
+
+/// Get the snapshot file path in the user's data directory
+fn snapshot_path() -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+
+    Some(data_dir.join("sysfetch").join("last-run.json"))
+}
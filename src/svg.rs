@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// Size of one terminal cell in SVG user units
+const CELL_WIDTH: f32 = 8.4;
+const CELL_HEIGHT: f32 = 17.0;
+
+const DEFAULT_FOREGROUND: &str = "#d0d0d0";
+const DEFAULT_BACKGROUND: &str = "#1e1e1e";
+
+/// Convert a rendered frame into an SVG approximating the terminal, with a
+/// background rect per colored run and one text element per run of cells
+/// sharing a style
+pub fn from_buffer(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}" font-family="ui-monospace, Menlo, Consolas, monospace" font-size="14">"#,
+        w = area.width as f32 * CELL_WIDTH,
+        h = area.height as f32 * CELL_HEIGHT,
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{DEFAULT_BACKGROUND}"/>"#
+    );
+
+    for y in 0..area.height {
+        let cells: Vec<&Cell> = (0..area.width).map(|x| buffer.get(x, y)).collect();
+        write_backgrounds(&mut svg, &cells, y);
+        write_text(&mut svg, &cells, y);
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_backgrounds(svg: &mut String, cells: &[&Cell], y: u16) {
+    let mut x = 0;
+    while x < cells.len() {
+        let Some(fill) = color(cells[x].bg) else {
+            x += 1;
+            continue;
+        };
+        let start = x;
+        while x < cells.len() && color(cells[x].bg).as_deref() == Some(fill.as_str()) {
+            x += 1;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{CELL_HEIGHT}" fill="{fill}"/>"#,
+            start as f32 * CELL_WIDTH,
+            y as f32 * CELL_HEIGHT,
+            (x - start) as f32 * CELL_WIDTH,
+        );
+    }
+}
+
+fn write_text(svg: &mut String, cells: &[&Cell], y: u16) {
+    let baseline = (y as f32 + 0.78) * CELL_HEIGHT;
+    let mut x = 0;
+    while x < cells.len() {
+        let cell = cells[x];
+        let width = cell.symbol().width();
+        if cell.symbol().trim().is_empty() {
+            x += 1;
+            continue;
+        }
+
+        // Wide glyphs get their own element so font metrics can't shift
+        // the following cells; narrow ones are batched by style
+        let start = x;
+        let mut text = String::new();
+        if width > 1 {
+            text.push_str(cell.symbol());
+            x += width;
+        } else {
+            while x < cells.len()
+                && cells[x].symbol().width() == 1
+                && cells[x].fg == cell.fg
+                && cells[x].modifier == cell.modifier
+            {
+                text.push_str(cells[x].symbol());
+                x += 1;
+            }
+        }
+
+        let fill = color(cell.fg).unwrap_or_else(|| DEFAULT_FOREGROUND.to_string());
+        let weight = if cell.modifier.contains(Modifier::BOLD) {
+            r#" font-weight="bold""#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{baseline:.1}" fill="{fill}"{weight} xml:space="preserve">{}</text>"#,
+            start as f32 * CELL_WIDTH,
+            escape(text.trim_end()),
+        );
+    }
+}
+
+/// Hex color for a terminal color, `None` for the terminal default
+fn color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Reset => return None,
+        Color::Black => "#000000",
+        Color::Red => "#cd3131",
+        Color::Green => "#0dbc79",
+        Color::Yellow => "#e5e510",
+        Color::Blue => "#2472c8",
+        Color::Magenta => "#bc3fbc",
+        Color::Cyan => "#11a8cd",
+        Color::Gray => "#e5e5e5",
+        Color::DarkGray => "#666666",
+        Color::LightRed => "#f14c4c",
+        Color::LightGreen => "#23d18b",
+        Color::LightYellow => "#f5f543",
+        Color::LightBlue => "#3b8eea",
+        Color::LightMagenta => "#d670d6",
+        Color::LightCyan => "#29b8db",
+        Color::White => "#ffffff",
+        Color::Rgb(r, g, b) => return Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::Indexed(index) => return Some(indexed(index)),
+    };
+    Some(hex.to_string())
+}
+
+/// Approximate an xterm 256-color palette entry
+fn indexed(index: u8) -> String {
+    const BASE: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::Gray,
+        Color::DarkGray,
+        Color::LightRed,
+        Color::LightGreen,
+        Color::LightYellow,
+        Color::LightBlue,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::White,
+    ];
+    match index {
+        0..=15 => color(BASE[index as usize]).unwrap_or_default(),
+        16..=231 => {
+            let level = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+            let index = index - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(index / 36),
+                level(index / 6 % 6),
+                level(index % 6)
+            )
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            format!("#{gray:02x}{gray:02x}{gray:02x}")
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
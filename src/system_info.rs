@@ -1,303 +1,3284 @@
 use crate::AppResult;
-use local_ip_address::local_ip;
+use crate::collector::{Collector, SystemCollector};
+use crate::config::{Config, DiskSort, IpVersion};
+use crate::format::percentage;
+use local_ip_address::{local_ip, local_ipv6};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::env;
-use std::process::Command;
-use sysinfo::System;
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{MAIN_SEPARATOR, Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, mpsc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use sysinfo::{
+    Components, Disks, MemoryRefreshKind, Networks, ProcessRefreshKind, ProcessStatus, RefreshKind,
+    System,
+};
+
+/// Placeholder for values masked by `--anonymize`
+const HIDDEN: &str = "hidden";
+
+/// Placeholders for values that could not be determined, the same whether
+/// the probe failed or was skipped
+const UNKNOWN_IP: &str = "Unknown IP";
+const UNKNOWN_SHELL: &str = "Unknown Shell";
+const UNKNOWN_TERMINAL: &str = "Unknown Terminal";
+
+/// Upper bound for the package manager update check
+const UPDATE_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Upper bound for `<shell> --version`
+const SHELL_VERSION_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Upper bound for the public IP lookup
+const PUBLIC_IP_TIMEOUT: Duration = Duration::from_secs(3);
 
 /// CPU information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CpuInfo {
+    /// Brand string as reported by the platform
     pub model: String,
+    /// `model` without trademarks, clock suffix and core count, for display
+    pub name: String,
+    /// Logical CPUs (threads) of this model
     pub cores: usize,
-    pub frequency: u64, // MHz
+    /// Physical cores, when known and the system has a single CPU model
+    pub physical_cores: Option<usize>,
+    /// Average clock in MHz over the cores of this model that report one
+    pub frequency_current: u64,
+    /// Nominal clock in MHz, when the platform reports it
+    pub frequency_base: Option<u64>,
+    /// Lowest and highest clock the hardware supports, in MHz (Linux cpufreq)
+    pub frequency_min: Option<u64>,
+    pub frequency_max: Option<u64>,
+    pub usage: f32, // percent, averaged over the cores of this model
+    /// Package power in watts, sampled at startup when enabled
+    pub power_draw: Option<f32>,
 }
 
 /// GPU information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GpuInfo {
     pub name: String,
     pub vendor: String,
+    /// Board power in watts, sampled at startup when enabled
+    pub power_draw: Option<f32>,
+    /// Video memory in bytes (NVIDIA, AMD on Linux)
+    pub vram_total: Option<u64>,
+    pub vram_used: Option<u64>,
+    /// Busy percentage at startup
+    pub utilization: Option<f32>,
+    /// Highest supported Metal family, e.g. "Metal 3" (macOS)
+    pub metal: Option<String>,
+    /// Graphics driver version (NVIDIA, Windows)
+    pub driver_version: Option<String>,
+}
+
+impl GpuInfo {
+    /// Placeholder for when the platform probe finds no GPU
+    pub fn unknown() -> Self {
+        Self {
+            name: "Unknown GPU".to_string(),
+            vendor: "Unknown".to_string(),
+            power_draw: None,
+            vram_total: None,
+            vram_used: None,
+            utilization: None,
+            metal: None,
+            driver_version: None,
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.name == "Unknown GPU"
+    }
+}
+
+/// Vendor and model of the machine (or of the mainboard, for machines
+/// without a product name) and its firmware version
+#[derive(Debug, Clone, Serialize)]
+pub struct MachineInfo {
+    pub manufacturer: String,
+    pub product: String,
+    pub bios_version: Option<String>,
+}
+
+/// Battery charge, summed over all batteries
+#[derive(Debug, Clone, Serialize)]
+pub struct BatteryInfo {
+    pub percentage: u8,
+    pub charging: bool,
+    /// Time until empty, or until full while charging, when estimated;
+    /// serialized as whole seconds
+    #[serde(serialize_with = "serialize_secs")]
+    pub time_remaining: Option<Duration>,
+}
+
+/// Serialize an optional duration as whole seconds rather than serde's
+/// `{secs, nanos}` pair
+fn serialize_secs<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| duration.as_secs())
+        .serialize(serializer)
+}
+
+impl BatteryInfo {
+    /// The remaining time as a duration like "2h 15m"
+    pub fn time_remaining_text(&self) -> Option<String> {
+        self.time_remaining
+            .map(|time| format_uptime(time.as_secs(), false))
+    }
+}
+
+/// Display information structure
+#[derive(Debug, Clone, Serialize)]
+pub struct DisplayInfo {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_hz: Option<f32>,
+    /// Desktop scaling, only known for the primary display
+    pub scale_factor: Option<f32>,
+}
+
+/// Disk information structure
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskInfo {
+    pub mount_point: String,
+    /// Filesystem type such as "ext4" or "apfs"
+    pub fs_type: String,
+    pub total: u64, // bytes
+    pub used: u64,  // bytes
+}
+
+/// A network interface with at least one address
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub mac: Option<String>,
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    /// Whether the default route leaves through this interface
+    pub default_route: bool,
+    /// Bytes per second received and sent between the last two samples;
+    /// `None` until a second sample exists (watch and refresh modes)
+    pub rx_rate: Option<u64>,
+    pub tx_rate: Option<u64>,
+}
+
+/// Terminal capabilities, reported only in machine-readable output
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminalCaps {
+    pub truecolor: bool,
+    pub columns: Option<u16>,
+    pub rows: Option<u16>,
+    pub term: Option<String>,
+    pub colorterm: Option<String>,
 }
 
 /// System information structure
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SystemInfo {
     pub os_name: String,
     pub os_version: String,
+    /// Distribution `ID` from os-release, e.g. "ubuntu" or "arch" (Linux only)
+    pub distro_id: Option<String>,
     pub os_arch: String,
     pub kernel_version: String,
     pub hostname: String,
+    /// Stable per-machine identifier; identifying, so opt-in only
+    pub machine_id: Option<String>,
+    /// Form factor such as "Laptop" or "Desktop"
+    pub chassis_type: Option<String>,
+    /// Hypervisor or container we run in, e.g. "KVM", "WSL2" or "Docker"
+    pub virtualization: Option<String>,
+    /// Make and model; `None` when the firmware doesn't say, as in many VMs
+    pub machine: Option<MachineInfo>,
     pub username: String,
     pub uptime: String,
+    pub uptime_seconds: u64,
+    /// Boot time and the time of collection as Unix timestamps
+    pub boot_time: u64,
+    pub local_time: u64,
+    /// Character locale, e.g. "en_US.UTF-8"
+    pub locale: Option<String>,
+    /// IANA time zone, e.g. "Europe/Berlin"
+    pub timezone: Option<String>,
+    /// Installed package count per package manager, e.g. ("dpkg", 1823)
+    pub packages: Vec<(String, usize)>,
     pub cpus: Vec<CpuInfo>,
+    /// Utilization across all CPUs in percent
+    pub global_usage: f32,
+    /// Utilization of each logical core in percent, in kernel order
+    pub core_usage: Vec<f32>,
+    /// Logical CPUs currently online, when some may be offlined (Linux only)
+    pub cores_online: Option<usize>,
+    /// CPU package temperature in °C, sampled at startup
+    pub cpu_temp: Option<f32>,
+    /// Whether CPU boost/turbo is enabled (Linux only)
+    pub turbo_enabled: Option<bool>,
+    /// Whether the CPU is being slowed down to shed heat (Linux only)
+    pub thermal_throttling: Option<bool>,
+    /// 1, 5 and 15 minute load averages (Unix only)
+    pub load_average: Option<(f64, f64, f64)>,
+    /// Processes (not threads), `None` when they were not enumerated
+    pub process_count: Option<usize>,
+    pub running_process_count: Option<usize>,
     pub memory_total: u64,
     pub memory_used: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+    /// Compression ratio of zram swap devices (Linux only)
+    pub zram_ratio: Option<f32>,
+    /// `None` on machines without a battery
+    pub battery: Option<BatteryInfo>,
     pub gpus: Vec<GpuInfo>,
+    pub displays: Vec<DisplayInfo>,
+    pub disks: Vec<DiskInfo>,
     pub local_ip: String,
+    /// Interfaces with addresses, the default route's first; loopback only
+    /// with `show-loopback`
+    pub interfaces: Vec<NetworkInterface>,
+    /// Address seen by the internet, looked up only with `--public-ip`
+    pub public_ip: Option<String>,
+    pub gateway: Option<String>,
+    pub dns_servers: Vec<String>,
+    /// Whether a VPN/tunnel interface is up
+    pub vpn_active: bool,
+    /// Name of the first tunnel interface, e.g. "wg0"
+    pub vpn_interface: Option<String>,
     pub shell: String,
+    /// e.g. "5.2.15" for bash
+    pub shell_version: Option<String>,
     pub terminal: String,
+    /// Launch directory with the home directory shortened to `~`
+    pub cwd: Option<String>,
+    /// Bits of entropy in the kernel pool (Linux only)
+    pub entropy_available: Option<u32>,
+    /// Allocated file handles system-wide (Linux only)
+    pub open_fds: Option<usize>,
+    /// Soft limit on open files for this process (Linux only)
+    pub fd_limit: Option<u64>,
+    /// Values of the sysctl keys allowlisted in the config (Linux only)
+    pub sysctls: Vec<(String, String)>,
+    /// Pending package updates; `None` when unknown or not requested
+    pub updates_available: Option<usize>,
+    /// Whether a Nerd Font appears to be installed; only informs icon defaults
+    pub nerd_font: bool,
+    /// Only surfaced through serialized output, never in the TUI
+    pub terminal_caps: TerminalCaps,
+    /// Fallbacks taken during collection, for consumers of the JSON output
+    pub warnings: Vec<Warning>,
+    /// Probes that came up empty because a command could not be run, e.g.
+    /// "GPU probe failed: lspci not found"; printed with `--verbose`
+    pub collection_errors: Vec<String>,
+}
+
+/// A value that could not be collected and was replaced by a fallback
+#[derive(Debug, Clone, Serialize)]
+pub struct Warning {
+    /// Name of the affected `SystemInfo` field, or `*` for all of them
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl Warning {
+    fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
 }
 
 impl SystemInfo {
-    /// Collect system information
-    pub fn collect() -> AppResult<Self> {
-        let mut sys = System::new_all();
-        sys.refresh_all();
+    /// Collect system information. Each probe degrades to "Unknown" or
+    /// `None` on its own and lands in `collection_errors`; only an
+    /// unreadable `--pid` process is an error
+    pub fn collect(config: &Config) -> AppResult<Self> {
+        if config.fast {
+            return Ok(Self::collect_fast(config));
+        }
+        Self::collect_from(config, Arc::new(SystemCollector::new()))
+    }
+
+    /// Like `collect`, reading CPUs, GPUs, memory, battery and uptime from
+    /// `collector`
+    pub fn collect_from(config: &Config, collector: Arc<dyn Collector>) -> AppResult<Self> {
+        Self::collect_in_stages(config, collector, |_| {})
+    }
+
+    /// `collect_from`, handing the values gathered so far to `publish`
+    /// after each stage, so a caller that stops waiting still has them
+    fn collect_in_stages(
+        config: &Config,
+        collector: Arc<dyn Collector>,
+        publish: impl Fn(&Self),
+    ) -> AppResult<Self> {
+        // Starts from the instantly available values and fills in the rest
+        let mut info = Self::collect_fast(config);
+
+        // Probes spawning external commands run on threads of their own
+        // while the rest is read from the kernel, and are joined at the end
+        let no_exec = config.no_exec;
+        let pending_packages = spawn_probe(
+            "package",
+            move || collect_packages(no_exec),
+            |packages| !packages.is_empty(),
+        );
+        let power_draw = config.power_draw;
+        let gpu_collector = Arc::clone(&collector);
+        let pending_gpus = spawn_probe(
+            "GPU",
+            move || gpu_collector.gpus(no_exec, power_draw),
+            |gpus| gpus.iter().any(|gpu| !gpu.is_unknown()),
+        );
+        let pending_displays = spawn_probe(
+            "display",
+            move || get_display_info_list(no_exec),
+            |displays| !displays.is_empty(),
+        );
+        let pending_nerd_font =
+            spawn_probe("font", move || detect_nerd_font(no_exec), |&found| found);
+        let pending_machine = spawn_probe(
+            "machine",
+            move || get_machine_info(no_exec),
+            Option::is_some,
+        );
+        // Opt-in, these hit the network
+        let pending_public_ip = (config.public_ip && !no_exec)
+            .then(|| spawn_probe("public IP", get_public_ip, Option::is_some));
+        let pending_updates = (config.check_updates && !no_exec)
+            .then(|| spawn_probe("update", get_updates_available, Option::is_some));
 
         // Basic system information
-        let os_name = System::name().unwrap_or_else(|| "Unknown".to_string());
-        let os_version = System::os_version().unwrap_or_else(|| "Unknown".to_string());
-        let os_arch = std::env::consts::ARCH.into();
-        let kernel_version = System::kernel_version().unwrap_or_else(|| "Unknown".to_string());
-        let hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
-        let username = env::var("USER")
-            .or_else(|_| env::var("USERNAME"))
-            .unwrap_or_else(|_| "Unknown".to_string());
+        info.hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
+        info.machine_id = probe(
+            &mut info.collection_errors,
+            "machine ID",
+            || get_machine_id(config.no_exec),
+            Option::is_some,
+        );
+        info.chassis_type = probe(
+            &mut info.collection_errors,
+            "chassis",
+            || get_chassis_type(config.no_exec),
+            Option::is_some,
+        );
+        info.virtualization = get_virtualization(config.no_exec);
+        let process_env = match config.pid {
+            Some(pid) => ProcessEnv::of_pid(pid)?,
+            None => ProcessEnv::current(),
+        };
+        info.username = process_env
+            .var("USER")
+            .or_else(|| process_env.var("USERNAME"))
+            .unwrap_or_else(|| "Unknown".to_string());
 
         // Uptime
-        let uptime_seconds = System::uptime();
-        let uptime = format_uptime(uptime_seconds);
+        info.uptime_seconds = collector.uptime();
+        info.uptime = format_uptime(info.uptime_seconds, config.show_seconds);
+        info.boot_time = collector.boot_time();
+        info.locale = get_locale(&process_env, config.no_exec);
+        info.timezone = get_timezone(&process_env);
+
+        // CPU information
+        info.cpus = collector.cpus();
+        info.cores_online = read_cpu_list("online");
+        info.cpu_temp = get_cpu_temp();
+        info.turbo_enabled = get_turbo_enabled();
+        info.thermal_throttling = get_thermal_throttling();
+        info.load_average = get_load_average();
+
+        // Memory information
+        let memory = collector.memory();
+        info.memory_total = memory.total;
+        info.memory_used = memory.used;
+        info.swap_total = memory.swap_total;
+        info.swap_used = memory.swap_used;
+        info.zram_ratio = get_zram_ratio();
+        publish(&info);
+
+        if config.count_processes {
+            (info.process_count, info.running_process_count) = count_processes(&mut System::new());
+        }
+        info.battery = probe(
+            &mut info.collection_errors,
+            "battery",
+            || collector.battery(config.no_exec),
+            Option::is_some,
+        );
+
+        // CPU power draw (opt-in, RAPL needs a short sampling interval);
+        // the GPU probe samples the GPUs
+        if config.power_draw {
+            // RAPL reports all packages together, so only attribute it to a single model
+            if let [cpu] = info.cpus.as_mut_slice() {
+                cpu.power_draw = get_cpu_power_draw();
+            }
+        }
+
+        // Disk information
+        info.disks = collect_disk_info();
+        sort_disks(&mut info.disks, config.disk_sort);
+
+        // Local IP address, from the configured interface when it exists
+        let addresses = collector.addresses();
+        let default_ip = collector.default_ip(config.ip_version);
+        info.local_ip = match config
+            .primary_interface
+            .as_deref()
+            .and_then(|name| interface_ip(&addresses, name, config.ip_version))
+        {
+            Some(ip) => ip.to_string(),
+            None => match &default_ip {
+                Ok(ip) => ip.to_string(),
+                Err(err) => {
+                    info.warnings.push(Warning::new(
+                        "local_ip",
+                        format!("could not resolve: {err}"),
+                    ));
+                    UNKNOWN_IP.to_string()
+                }
+            },
+        };
+        info.interfaces = collect_interfaces(&addresses, default_ip.ok(), config.show_loopback);
+        info.gateway = probe(
+            &mut info.collection_errors,
+            "gateway",
+            || get_default_gateway(config.no_exec),
+            Option::is_some,
+        );
+        info.dns_servers = probe(
+            &mut info.collection_errors,
+            "DNS",
+            || get_dns_servers(config.no_exec),
+            |servers| !servers.is_empty(),
+        );
+        info.vpn_interface = detect_vpn_interface(&addresses);
+        info.vpn_active = info.vpn_interface.is_some();
+        publish(&info);
+
+        // Shell and Terminal information
+        info.shell = probe(
+            &mut info.collection_errors,
+            "shell",
+            || get_shell_info(&process_env, config.no_exec),
+            |shell| shell != UNKNOWN_SHELL,
+        );
+        info.shell_version = get_shell_version(&process_env, &info.shell, config.no_exec);
+        info.terminal = probe(
+            &mut info.collection_errors,
+            "terminal",
+            || get_terminal_info(&process_env, config.no_exec),
+            |terminal| terminal != UNKNOWN_TERMINAL,
+        );
+        if info.shell == UNKNOWN_SHELL {
+            info.warnings
+                .push(Warning::new("shell", "could not determine the shell"));
+        }
+        if info.terminal == UNKNOWN_TERMINAL {
+            info.warnings
+                .push(Warning::new("terminal", "could not determine the terminal"));
+        }
+        info.cwd = get_cwd();
+        info.entropy_available = get_entropy_available();
+        (info.open_fds, info.fd_limit) = get_fd_usage();
+        info.sysctls = get_sysctls(&config.sysctls);
+
+        info.terminal_caps = get_terminal_caps();
+        publish(&info);
+
+        // A panicking probe leaves its values empty, like a failing one
+        let errors = &mut info.collection_errors;
+        info.packages = pending_packages.join(errors).unwrap_or_default();
+        info.gpus = pending_gpus.join(errors).unwrap_or_default();
+        if !config.no_exec && info.gpus.iter().all(GpuInfo::is_unknown) {
+            info.warnings
+                .push(Warning::new("gpus", "no GPU found by the platform probe"));
+        }
+        info.displays = pending_displays.join(errors).unwrap_or_default();
+        info.nerd_font = pending_nerd_font.join(errors).unwrap_or(false);
+        info.machine = pending_machine.join(errors).flatten();
+        info.public_ip = pending_public_ip.and_then(|pending| {
+            let ip = pending.join(errors).flatten();
+            if ip.is_none() {
+                info.warnings
+                    .push(Warning::new("public_ip", "lookup failed or timed out"));
+            }
+            ip
+        });
+        info.updates_available = pending_updates.and_then(|pending| {
+            let updates = pending.join(errors).flatten();
+            if updates.is_none() {
+                info.warnings.push(Warning::new(
+                    "updates_available",
+                    "package manager query failed or timed out",
+                ));
+            }
+            updates
+        });
+        publish(&info);
+
+        // Sampled last, the longer since `collector` was created the better
+        (info.global_usage, info.core_usage) = collector.cpu_usage(&mut info.cpus);
+
+        Ok(info)
+    }
+
+    /// Collect on a worker thread; when the whole collection exceeds
+    /// `timeout`, return the values it had gathered by then with a warning
+    pub fn collect_with_timeout(
+        config: &Config,
+        collector: Arc<dyn Collector>,
+        timeout: Duration,
+    ) -> AppResult<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let partial = Arc::new(Mutex::new(None));
+        let worker_partial = Arc::clone(&partial);
+        let worker_config = config.clone();
+        thread::spawn(move || {
+            let result = Self::collect_in_stages(&worker_config, collector, |info| {
+                *worker_partial
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner) = Some(info.clone());
+            });
+            // Errors aren't `Send`, so they cross the thread boundary as strings
+            let _ = sender.send(result.map_err(|err| err.to_string()));
+        });
+
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result.map_err(Into::into),
+            Err(_) => {
+                // Before the first stage, only the instant values are there
+                let mut info = partial
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .take()
+                    .unwrap_or_else(|| Self::collect_fast(config));
+                let message = format!(
+                    "collection timed out after {}s; values not gathered by then are missing",
+                    timeout.as_secs()
+                );
+                info.collection_errors.push(message.clone());
+                info.warnings.push(Warning::new("*", message));
+                Ok(info)
+            }
+        }
+    }
+
+    /// Only the values that can be read without spawning processes or
+    /// scanning devices: OS, kernel, uptime, memory and swap. Everything
+    /// else is left empty or "Unknown"
+    fn collect_fast(config: &Config) -> Self {
+        let sys = System::new_with_specifics(
+            RefreshKind::new().with_memory(MemoryRefreshKind::new().with_ram().with_swap()),
+        );
+
+        let (os_name, os_version, distro_id) = get_os_identity();
+        Self {
+            os_name,
+            os_version,
+            distro_id,
+            os_arch: std::env::consts::ARCH.into(),
+            kernel_version: System::kernel_version().unwrap_or_else(|| "Unknown".to_string()),
+            hostname: "Unknown".to_string(),
+            machine_id: None,
+            chassis_type: None,
+            virtualization: None,
+            machine: None,
+            username: "Unknown".to_string(),
+            uptime: format_uptime(System::uptime(), config.show_seconds),
+            uptime_seconds: System::uptime(),
+            boot_time: System::boot_time(),
+            local_time: unix_now(),
+            locale: None,
+            timezone: None,
+            packages: Vec::new(),
+            cpus: Vec::new(),
+            global_usage: 0.0,
+            core_usage: Vec::new(),
+            cores_online: None,
+            cpu_temp: None,
+            turbo_enabled: None,
+            thermal_throttling: None,
+            load_average: None,
+            process_count: None,
+            running_process_count: None,
+            memory_total: sys.total_memory(),
+            memory_used: sys.used_memory(),
+            swap_total: sys.total_swap(),
+            swap_used: sys.used_swap(),
+            zram_ratio: None,
+            battery: None,
+            gpus: Vec::new(),
+            displays: Vec::new(),
+            disks: Vec::new(),
+            local_ip: UNKNOWN_IP.to_string(),
+            interfaces: Vec::new(),
+            public_ip: None,
+            gateway: None,
+            dns_servers: Vec::new(),
+            vpn_active: false,
+            vpn_interface: None,
+            shell: UNKNOWN_SHELL.to_string(),
+            shell_version: None,
+            terminal: UNKNOWN_TERMINAL.to_string(),
+            cwd: None,
+            entropy_available: None,
+            open_fds: None,
+            fd_limit: None,
+            sysctls: Vec::new(),
+            updates_available: None,
+            nerd_font: false,
+            terminal_caps: TerminalCaps {
+                truecolor: false,
+                columns: None,
+                rows: None,
+                term: None,
+                colorterm: None,
+            },
+            warnings: Vec::new(),
+            collection_errors: Vec::new(),
+        }
+    }
+
+    /// Fixed, made-up values for demos and screenshots
+    pub fn sample() -> Self {
+        const GIB: u64 = 1024 * 1024 * 1024;
+
+        Self {
+            os_name: "Arch Linux".to_string(),
+            os_version: "rolling".to_string(),
+            distro_id: Some("arch".to_string()),
+            os_arch: "x86_64".to_string(),
+            kernel_version: "6.9.3-arch1-1".to_string(),
+            hostname: "ferris-desktop".to_string(),
+            machine_id: Some("0123456789abcdef0123456789abcdef".to_string()),
+            chassis_type: Some("Desktop".to_string()),
+            virtualization: None,
+            machine: Some(MachineInfo {
+                manufacturer: "ASUSTeK COMPUTER INC.".to_string(),
+                product: "ROG STRIX B650E-F GAMING WIFI".to_string(),
+                bios_version: Some("2613".to_string()),
+            }),
+            username: "ferris".to_string(),
+            uptime: format_uptime(3 * 3600 + 25 * 60, false),
+            uptime_seconds: 3 * 3600 + 25 * 60,
+            boot_time: 1_717_229_640,
+            local_time: 1_717_229_640 + 3 * 3600 + 25 * 60,
+            locale: Some("en_US.UTF-8".to_string()),
+            timezone: Some("Europe/Berlin".to_string()),
+            packages: vec![("pacman".to_string(), 1123), ("flatpak".to_string(), 12)],
+            cpus: vec![CpuInfo {
+                model: "AMD Ryzen 7 7800X3D 8-Core Processor".to_string(),
+                name: "AMD Ryzen 7 7800X3D".to_string(),
+                cores: 16,
+                physical_cores: Some(8),
+                frequency_current: 4850,
+                frequency_base: Some(4200),
+                frequency_min: Some(545),
+                frequency_max: Some(5050),
+                usage: 12.0,
+                power_draw: Some(45.0),
+            }],
+            global_usage: 12.0,
+            core_usage: vec![
+                31.0, 4.0, 18.0, 2.0, 57.0, 9.0, 12.0, 3.0, 86.0, 6.0, 11.0, 2.0, 24.0, 5.0, 7.0,
+                15.0,
+            ],
+            cores_online: Some(16),
+            cpu_temp: Some(54.0),
+            turbo_enabled: Some(true),
+            thermal_throttling: Some(false),
+            load_average: Some((0.52, 0.61, 0.48)),
+            process_count: Some(312),
+            running_process_count: Some(3),
+            memory_total: 32 * GIB,
+            memory_used: 9 * GIB,
+            swap_total: 8 * GIB,
+            swap_used: GIB / 2,
+            zram_ratio: Some(2.8),
+            battery: None,
+            gpus: vec![GpuInfo {
+                name: "NVIDIA GeForce RTX 4070".to_string(),
+                vendor: "NVIDIA".to_string(),
+                power_draw: Some(38.0),
+                vram_total: Some(12 * GIB),
+                vram_used: Some(3 * GIB),
+                utilization: Some(23.0),
+                metal: None,
+                driver_version: Some("550.90".to_string()),
+            }],
+            displays: vec![DisplayInfo {
+                width: 2560,
+                height: 1440,
+                refresh_hz: Some(144.0),
+                scale_factor: Some(1.0),
+            }],
+            disks: vec![
+                DiskInfo {
+                    mount_point: "/".to_string(),
+                    fs_type: "ext4".to_string(),
+                    total: 1000 * GIB,
+                    used: 412 * GIB,
+                },
+                DiskInfo {
+                    mount_point: "/home".to_string(),
+                    fs_type: "ext4".to_string(),
+                    total: 2000 * GIB,
+                    used: 1310 * GIB,
+                },
+            ],
+            // Documentation address ranges (RFC 5737)
+            local_ip: "192.0.2.42".to_string(),
+            interfaces: vec![
+                NetworkInterface {
+                    name: "eth0".to_string(),
+                    mac: Some("52:54:00:12:34:56".to_string()),
+                    ipv4: vec!["192.0.2.42".to_string()],
+                    ipv6: vec!["2001:db8::42".to_string()],
+                    default_route: true,
+                    rx_rate: Some(1_258_291),
+                    tx_rate: Some(245_760),
+                },
+                NetworkInterface {
+                    name: "wg0".to_string(),
+                    mac: None,
+                    ipv4: vec!["198.51.100.2".to_string()],
+                    ipv6: Vec::new(),
+                    default_route: false,
+                    rx_rate: Some(0),
+                    tx_rate: Some(0),
+                },
+            ],
+            public_ip: Some("203.0.113.7".to_string()),
+            gateway: Some("192.0.2.1".to_string()),
+            dns_servers: vec!["198.51.100.53".to_string()],
+            vpn_active: true,
+            vpn_interface: Some("wg0".to_string()),
+            shell: "zsh".to_string(),
+            shell_version: Some("5.9".to_string()),
+            terminal: "WezTerm".to_string(),
+            cwd: Some("~/projects/rsysfetch".to_string()),
+            entropy_available: Some(256),
+            open_fds: Some(4128),
+            fd_limit: Some(1024),
+            sysctls: vec![("vm.swappiness".to_string(), "60".to_string())],
+            updates_available: Some(7),
+            nerd_font: false,
+            terminal_caps: TerminalCaps {
+                truecolor: true,
+                columns: Some(120),
+                rows: Some(40),
+                term: Some("xterm-256color".to_string()),
+                colorterm: Some("truecolor".to_string()),
+            },
+            warnings: Vec::new(),
+            collection_errors: Vec::new(),
+        }
+    }
+
+    /// Update the fast-changing values from an already refreshed `System`
+    pub fn refresh_dynamic(&mut self, sys: &System) {
+        let mut cpus = collect_cpu_info(sys);
+        // Power, core count and clock limits are only read at startup, keep them across refreshes
+        for cpu in &mut cpus {
+            if let Some(old) = self.cpus.iter().find(|old| old.model == cpu.model) {
+                cpu.power_draw = old.power_draw;
+                cpu.physical_cores = old.physical_cores;
+                cpu.frequency_base = old.frequency_base;
+                cpu.frequency_min = old.frequency_min;
+                cpu.frequency_max = old.frequency_max;
+            }
+        }
+        self.cpus = cpus;
+        self.global_usage = sys.global_cpu_info().cpu_usage();
+        self.core_usage = sys.cpus().iter().map(|core| core.cpu_usage()).collect();
+        // Only the trip points are cheap enough to poll; a counter-based
+        // reading from startup is kept as is
+        if let Some(throttling) = trip_point_throttling() {
+            self.thermal_throttling = Some(throttling);
+        }
+        self.load_average = get_load_average();
+        self.local_time = unix_now();
+        self.memory_total = sys.total_memory();
+        self.memory_used = sys.used_memory();
+        self.swap_total = sys.total_swap();
+        self.swap_used = sys.used_swap();
+    }
+
+    /// Set the transfer rates from a `Networks` refreshed `elapsed` after
+    /// the previous refresh; its counters are the bytes moved in between
+    pub fn refresh_network_rates(&mut self, networks: &Networks, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        if secs <= 0.0 {
+            return;
+        }
+        for interface in &mut self.interfaces {
+            let Some(data) = networks.list().get(&interface.name) else {
+                continue;
+            };
+            interface.rx_rate = Some((data.received() as f64 / secs).round() as u64);
+            interface.tx_rate = Some((data.transmitted() as f64 / secs).round() as u64);
+        }
+    }
+
+    /// Mask identifying fields for screenshots and shared output
+    pub fn anonymize(&mut self) {
+        if let Some(cwd) = &mut self.cwd {
+            *cwd = mask_path_user(cwd, &self.username);
+        }
+        self.hostname = HIDDEN.to_string();
+        if self.machine_id.is_some() {
+            self.machine_id = Some(HIDDEN.to_string());
+        }
+        self.username = HIDDEN.to_string();
+        self.mask_addresses();
+    }
+
+    /// Mask only the network addresses
+    pub fn mask_addresses(&mut self) {
+        self.local_ip = HIDDEN.to_string();
+        for interface in &mut self.interfaces {
+            if interface.mac.is_some() {
+                interface.mac = Some(HIDDEN.to_string());
+            }
+            for ip in interface.ipv4.iter_mut().chain(&mut interface.ipv6) {
+                *ip = HIDDEN.to_string();
+            }
+        }
+        if self.public_ip.is_some() {
+            self.public_ip = Some(HIDDEN.to_string());
+        }
+        if self.gateway.is_some() {
+            self.gateway = Some(HIDDEN.to_string());
+        }
+        for server in &mut self.dns_servers {
+            *server = HIDDEN.to_string();
+        }
+    }
+}
+
+/// Replace the path component matching the username with "user"
+fn mask_path_user(path: &str, username: &str) -> String {
+    path.split_inclusive(['/', '\\'])
+        .map(|part| {
+            if part.trim_end_matches(['/', '\\']) == username {
+                part.replacen(username, "user", 1)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Format uptime; with `show_seconds`, durations under an hour include
+/// their seconds ("45s", "1m 30s") rather than rounding down to minutes
+pub(crate) fn format_uptime(seconds: u64, show_seconds: bool) -> String {
+    let days = seconds / 86400;
+    let hours = (seconds % 86400) / 3600;
+    let minutes = (seconds % 3600) / 60;
+
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if !show_seconds {
+        format!("{}m", minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds % 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Get the battery state
+pub(crate) fn get_battery_info(no_exec: bool) -> Option<BatteryInfo> {
+    if cfg!(target_os = "linux") {
+        get_battery_info_linux()
+    } else if cfg!(target_os = "macos") {
+        let output = run_command(no_exec, Command::new("pmset").args(["-g", "batt"]))?;
+        parse_pmset_battery(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        None
+    }
+}
+
+/// Sum the batteries under `/sys/class/power_supply`, using energy (µWh, µW)
+/// or charge (µAh, µA) counters depending on what the driver exposes
+fn get_battery_info_linux() -> Option<BatteryInfo> {
+    let batteries: Vec<PathBuf> = fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            fs::read_to_string(path.join("type")).is_ok_and(|kind| kind.trim() == "Battery")
+        })
+        .collect();
+    if batteries.is_empty() {
+        return None;
+    }
+
+    let charging = batteries.iter().any(|path| {
+        fs::read_to_string(path.join("status")).is_ok_and(|status| status.trim() == "Charging")
+    });
+    let sum = |names: [&str; 2]| -> Option<u64> {
+        batteries
+            .iter()
+            .map(|path| names.iter().find_map(|name| read_u64(&path.join(name))))
+            .sum()
+    };
+    let now = sum(["energy_now", "charge_now"]);
+    let full = sum(["energy_full", "charge_full"]);
+    let rate = sum(["power_now", "current_now"]).filter(|&rate| rate > 0);
+
+    let percentage = match (now, full) {
+        (Some(now), Some(full)) if full > 0 => (now * 100 / full).min(100) as u8,
+        // Some drivers only report a capacity percentage
+        _ => {
+            let capacities: Vec<u64> = batteries
+                .iter()
+                .filter_map(|path| read_u64(&path.join("capacity")))
+                .collect();
+            (capacities.iter().sum::<u64>() / capacities.len().max(1) as u64).min(100) as u8
+        }
+    };
+    let time_remaining = match (now, full, rate) {
+        (Some(now), Some(full), Some(rate)) => {
+            let left = if charging {
+                full.saturating_sub(now)
+            } else {
+                now
+            };
+            Some(Duration::from_secs(left * 3600 / rate))
+        }
+        _ => None,
+    };
+
+    Some(BatteryInfo {
+        percentage,
+        charging,
+        time_remaining,
+    })
+}
+
+/// Parse `pmset -g batt`, whose battery line looks like
+/// " -InternalBattery-0 (id=123)\t85%; charging; 1:23 remaining present: true"
+fn parse_pmset_battery(output: &str) -> Option<BatteryInfo> {
+    let line = output
+        .lines()
+        .find(|line| line.contains("InternalBattery"))?;
+    let (_, details) = line.split_once('\t')?;
+    let mut parts = details.split(';').map(str::trim);
+
+    let percentage = parts.next()?.strip_suffix('%')?.parse().ok()?;
+    let charging = parts.next()? == "charging";
+    let time_remaining = parts
+        .next()
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|time| time.split_once(':'))
+        .and_then(|(hours, minutes)| {
+            Some((hours.parse::<u64>().ok()?, minutes.parse::<u64>().ok()?))
+        })
+        .map(|(hours, minutes)| Duration::from_secs(hours * 3600 + minutes * 60));
+
+    Some(BatteryInfo {
+        percentage,
+        charging,
+        time_remaining,
+    })
+}
+
+/// OS name, version and distribution ID; on Linux taken from os-release,
+/// which names the actual distribution, falling back to sysinfo
+fn get_os_identity() -> (String, String, Option<String>) {
+    let name = System::name().unwrap_or_else(|| "Unknown".to_string());
+    let version = System::os_version().unwrap_or_else(|| "Unknown".to_string());
+    if !cfg!(target_os = "linux") {
+        return (name, version, None);
+    }
+
+    match ["/etc/os-release", "/usr/lib/os-release"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+    {
+        Some(content) => os_identity_from_release(&parse_os_release(&content), name, version),
+        None => (name, version, None),
+    }
+}
+
+/// OS name, version and distribution ID from parsed os-release, with
+/// sysinfo's name and version for the keys it lacks
+fn os_identity_from_release(
+    release: &HashMap<String, String>,
+    name: String,
+    version: String,
+) -> (String, String, Option<String>) {
+    let distro_id = release.get("ID").cloned();
+    let os_name = release.get("NAME").cloned().unwrap_or(name);
+    // PRETTY_NAME carries the marketing version ("24.04.1 LTS"); rolling
+    // releases such as Arch have none and keep sysinfo's "rolling"
+    let os_version = release
+        .get("PRETTY_NAME")
+        .and_then(|pretty| pretty.strip_prefix(os_name.as_str()))
+        .map(str::trim)
+        .filter(|rest| !rest.is_empty())
+        .map(str::to_string)
+        .or_else(|| release.get("VERSION_ID").cloned())
+        .unwrap_or(version);
+    (os_name, os_version, distro_id)
+}
+
+/// Parse the `KEY=value` lines of os-release, unquoting the values
+fn parse_os_release(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .or_else(|| {
+                    value
+                        .strip_prefix('\'')
+                        .and_then(|value| value.strip_suffix('\''))
+                })
+                .unwrap_or(value);
+            (key.trim().to_string(), value.replace("\\\"", "\""))
+        })
+        .collect()
+}
+
+/// Get the stable machine identifier
+fn get_machine_id(no_exec: bool) -> Option<String> {
+    if cfg!(target_os = "linux") {
+        ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .map(|id| id.trim().to_string())
+            .find(|id| !id.is_empty())
+    } else if cfg!(target_os = "macos") {
+        // Line looks like: "IOPlatformUUID" = "XXXXXXXX-..."
+        let output = run_command(
+            no_exec,
+            Command::new("ioreg").args(["-rd1", "-c", "IOPlatformExpertDevice"]),
+        )?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.contains("\"IOPlatformUUID\""))
+            .and_then(|line| line.split('"').nth(3))
+            .map(str::to_string)
+    } else if cfg!(target_os = "windows") {
+        // Line looks like: "    MachineGuid    REG_SZ    xxxxxxxx-..."
+        let output = run_command(
+            no_exec,
+            Command::new("reg").args([
+                "query",
+                "HKLM\\SOFTWARE\\Microsoft\\Cryptography",
+                "/v",
+                "MachineGuid",
+            ]),
+        )?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with("MachineGuid"))
+            .and_then(|line| line.split_whitespace().nth(2))
+            .map(str::to_string)
+    } else {
+        None
+    }
+}
+
+/// Detect the machine form factor
+fn get_chassis_type(no_exec: bool) -> Option<String> {
+    if cfg!(target_os = "linux") {
+        get_chassis_type_linux()
+    } else if cfg!(target_os = "windows") {
+        get_chassis_type_windows(no_exec)
+    } else if cfg!(target_os = "macos") {
+        get_chassis_type_macos(no_exec)
+    } else {
+        None
+    }
+    .map(str::to_string)
+}
+
+/// Read the chassis type from DMI on Linux
+fn get_chassis_type_linux() -> Option<&'static str> {
+    // Hypervisors usually report a generic chassis, so check the vendor first
+    if is_virtual_vendor(&dmi_system_name()) {
+        return Some("VM");
+    }
+
+    let code = read_dmi("chassis_type")?.parse().ok()?;
+    chassis_from_dmi_code(code)
+}
+
+/// Read the chassis type from WMI on Windows
+fn get_chassis_type_windows(no_exec: bool) -> Option<&'static str> {
+    let enclosures = cim_instances(no_exec, "Win32_SystemEnclosure", None, &["ChassisTypes"]);
+    // An array such as [10], the first entry is the chassis itself
+    let code = enclosures
+        .first()?
+        .get("ChassisTypes")?
+        .as_array()?
+        .first()?
+        .as_u64()? as u32;
+    chassis_from_dmi_code(code)
+}
+
+/// Guess the chassis type from the model identifier on macOS
+fn get_chassis_type_macos(no_exec: bool) -> Option<&'static str> {
+    let output = run_command(no_exec, Command::new("sysctl").args(["-n", "hw.model"]))?;
+    let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    if model.is_empty() {
+        None
+    } else if is_virtual_vendor(&model) {
+        Some("VM")
+    } else if model.starts_with("MacBook") {
+        Some("Laptop")
+    } else if model.starts_with("Xserve") {
+        Some("Server")
+    } else {
+        Some("Desktop")
+    }
+}
+
+/// Whether a vendor or model string belongs to a hypervisor
+fn is_virtual_vendor(vendor: &str) -> bool {
+    hypervisor_from_vendor(vendor).is_some()
+}
+
+/// Read the make, model and firmware version of the machine
+fn get_machine_info(no_exec: bool) -> Option<MachineInfo> {
+    if cfg!(target_os = "linux") {
+        get_machine_info_linux()
+    } else if cfg!(target_os = "windows") {
+        get_machine_info_windows(no_exec)
+    } else if cfg!(target_os = "macos") {
+        get_machine_info_macos(no_exec)
+    } else {
+        None
+    }
+}
+
+/// Read the machine from DMI, falling back to the mainboard on self-built
+/// PCs whose product name is a placeholder
+fn get_machine_info_linux() -> Option<MachineInfo> {
+    let (manufacturer, product) = match (read_dmi("sys_vendor"), read_dmi("product_name")) {
+        (Some(vendor), Some(product)) => (vendor, product),
+        _ => (read_dmi("board_vendor")?, read_dmi("board_name")?),
+    };
+    Some(MachineInfo {
+        manufacturer,
+        product,
+        bios_version: read_dmi("bios_version"),
+    })
+}
+
+/// A DMI attribute from `/sys/class/dmi/id`, unless it is missing or a
+/// vendor placeholder
+fn read_dmi(name: &str) -> Option<String> {
+    let value = fs::read_to_string(format!("/sys/class/dmi/id/{name}")).ok()?;
+    let value = value.trim();
+    (!is_placeholder_dmi(value)).then(|| value.to_string())
+}
+
+/// The DMI system vendor and product name, e.g. "QEMU Standard PC (Q35 + ICH9, 2009)"
+fn dmi_system_name() -> String {
+    [read_dmi("sys_vendor"), read_dmi("product_name")]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether a DMI string is a vendor placeholder rather than a real value
+fn is_placeholder_dmi(value: &str) -> bool {
+    let value = value.to_lowercase();
+    value.is_empty()
+        || [
+            "to be filled by o.e.m.",
+            "default string",
+            "system product name",
+            "system manufacturer",
+            "not applicable",
+            "not specified",
+            "none",
+            "o.e.m.",
+        ]
+        .contains(&value.as_str())
+}
+
+/// Read the machine from WMI on Windows
+fn get_machine_info_windows(no_exec: bool) -> Option<MachineInfo> {
+    let systems = cim_instances(
+        no_exec,
+        "Win32_ComputerSystem",
+        None,
+        &["Manufacturer", "Model"],
+    );
+    let system = systems.first()?;
+    let bios = cim_instances(no_exec, "Win32_BIOS", None, &["SMBIOSBIOSVersion"]);
+    Some(MachineInfo {
+        manufacturer: cim_string(system, "Manufacturer")
+            .filter(|value| !is_placeholder_dmi(value))?,
+        product: cim_string(system, "Model").filter(|value| !is_placeholder_dmi(value))?,
+        bios_version: bios
+            .first()
+            .and_then(|bios| cim_string(bios, "SMBIOSBIOSVersion")),
+    })
+}
+
+/// Read the model from `system_profiler SPHardwareDataType -json` on macOS
+fn get_machine_info_macos(no_exec: bool) -> Option<MachineInfo> {
+    let output = run_command(
+        no_exec,
+        Command::new("system_profiler").args(["SPHardwareDataType", "-json"]),
+    )?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    let hardware = json.get("SPHardwareDataType")?.get(0)?;
+    let field = |key: &str| hardware.get(key)?.as_str().map(str::to_string);
+
+    // e.g. "MacBook Pro (MacBookPro18,3)"
+    let product = match (field("machine_name"), field("machine_model")) {
+        (Some(name), Some(model)) => format!("{name} ({model})"),
+        (name, model) => name.or(model)?,
+    };
+    Some(MachineInfo {
+        manufacturer: "Apple".to_string(),
+        product,
+        bios_version: field("boot_rom_version"),
+    })
+}
+
+/// Detect the container or hypervisor we run in; containers win over the
+/// VM they may be running in, since they are what the values describe
+fn get_virtualization(no_exec: bool) -> Option<String> {
+    if cfg!(target_os = "linux") {
+        get_virtualization_linux(no_exec)
+    } else if cfg!(target_os = "windows") {
+        let systems = cim_instances(
+            no_exec,
+            "Win32_ComputerSystem",
+            None,
+            &["Manufacturer", "Model"],
+        );
+        let system = systems.first()?;
+        let vendor = ["Manufacturer", "Model"]
+            .iter()
+            .filter_map(|key| cim_string(system, key))
+            .collect::<Vec<_>>()
+            .join(" ");
+        hypervisor_from_vendor(&vendor).map(str::to_string)
+    } else if cfg!(target_os = "macos") {
+        let output = run_command(no_exec, Command::new("sysctl").args(["-n", "hw.model"]))?;
+        hypervisor_from_vendor(&String::from_utf8_lossy(&output.stdout)).map(str::to_string)
+    } else {
+        None
+    }
+}
+
+/// WSL from the kernel version, containers from their marker files and
+/// cgroups, then hypervisors from `systemd-detect-virt` or DMI
+fn get_virtualization_linux(no_exec: bool) -> Option<String> {
+    let kernel = fs::read_to_string("/proc/version")
+        .unwrap_or_default()
+        .to_lowercase();
+    if kernel.contains("microsoft") {
+        // WSL1 kernels are "-Microsoft", WSL2 ones "-microsoft-standard-WSL2"
+        let wsl = if kernel.contains("wsl2") || kernel.contains("microsoft-standard") {
+            "WSL2"
+        } else {
+            "WSL"
+        };
+        return Some(wsl.to_string());
+    }
+
+    if Path::new("/.dockerenv").exists() {
+        return Some("Docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("Podman".to_string());
+    }
+    let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+    if let Some(container) = container_from_cgroup(&cgroup) {
+        return Some(container.to_string());
+    }
+
+    if let Some(output) = run_command(no_exec, &mut Command::new("systemd-detect-virt")) {
+        // Prints "none" and exits with 1 on bare metal
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return (output.status.success() && !name.is_empty() && name != "none")
+            .then(|| virt_display_name(&name));
+    }
+
+    hypervisor_from_vendor(&dmi_system_name()).map(str::to_string)
+}
+
+/// Container runtime named in the cgroup paths of PID 1
+fn container_from_cgroup(cgroup: &str) -> Option<&'static str> {
+    [
+        ("kubepods", "Kubernetes"),
+        ("docker", "Docker"),
+        ("libpod", "Podman"),
+        ("lxc", "LXC"),
+        ("containerd", "containerd"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| cgroup.contains(marker))
+    .map(|(_, name)| name)
+}
+
+/// Friendly name for a `systemd-detect-virt` identifier
+fn virt_display_name(id: &str) -> String {
+    match id {
+        "kvm" => "KVM",
+        "qemu" => "QEMU",
+        "vmware" => "VMware",
+        "oracle" => "VirtualBox",
+        "microsoft" => "Hyper-V",
+        "xen" => "Xen",
+        "parallels" => "Parallels",
+        "bochs" => "Bochs",
+        "amazon" => "AWS Nitro",
+        "docker" => "Docker",
+        "podman" => "Podman",
+        "lxc" | "lxc-libvirt" => "LXC",
+        "systemd-nspawn" => "nspawn",
+        "wsl" => "WSL",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Hypervisor named by a vendor or model string, e.g. "QEMU Standard PC"
+fn hypervisor_from_vendor(vendor: &str) -> Option<&'static str> {
+    let vendor = vendor.to_lowercase();
+    [
+        ("kvm", "KVM"),
+        ("qemu", "QEMU"),
+        ("vmware", "VMware"),
+        ("virtualbox", "VirtualBox"),
+        ("xen", "Xen"),
+        ("parallels", "Parallels"),
+        ("bochs", "Bochs"),
+        ("virtual machine", "Hyper-V"),
+    ]
+    .into_iter()
+    .find(|(marker, _)| vendor.contains(marker))
+    .map(|(_, name)| name)
+}
+
+/// Map an SMBIOS chassis type code to a friendly name
+fn chassis_from_dmi_code(code: u32) -> Option<&'static str> {
+    match code {
+        3 | 4 | 5 | 6 | 7 | 13 | 15 | 16 | 24 | 34 | 35 | 36 => Some("Desktop"),
+        8 | 9 | 10 | 11 | 14 | 30 | 31 | 32 => Some("Laptop"),
+        17 | 23 | 25 | 28 | 29 => Some("Server"),
+        _ => None,
+    }
+}
+
+/// Current time as a Unix timestamp
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Load averages from sysinfo; Windows has no such counter and reports zeros
+fn get_load_average() -> Option<(f64, f64, f64)> {
+    if cfg!(target_os = "windows") {
+        return None;
+    }
+    let load = System::load_average();
+    Some((load.one, load.five, load.fifteen))
+}
+
+/// Count processes and those currently running. Only the bare process list
+/// is refreshed, which keeps it far cheaper than a full process scan.
+fn count_processes(sys: &mut System) -> (Option<usize>, Option<usize>) {
+    sys.refresh_processes_specifics(ProcessRefreshKind::new());
+    // Linux lists threads alongside processes
+    let processes: Vec<_> = sys
+        .processes()
+        .values()
+        .filter(|process| process.thread_kind().is_none())
+        .collect();
+    if processes.is_empty() {
+        return (None, None);
+    }
+
+    let running = processes
+        .iter()
+        .filter(|process| process.status() == ProcessStatus::Run)
+        .count();
+    (Some(processes.len()), Some(running))
+}
+
+/// Collect CPU information, one entry per model in the order of each
+/// model's first core, so multi-socket and hybrid systems list the same
+/// way on every run
+pub(crate) fn collect_cpu_info(sys: &System) -> Vec<CpuInfo> {
+    // Per model: the info plus the sum and count of the non-zero clocks
+    let mut groups: Vec<(CpuInfo, u64, u64)> = Vec::new();
+
+    // Group CPUs by model to handle multi-core processors
+    for cpu in sys.cpus() {
+        let index = match groups
+            .iter()
+            .position(|(group, _, _)| group.model == cpu.brand())
+        {
+            Some(index) => index,
+            None => {
+                groups.push((
+                    CpuInfo {
+                        model: cpu.brand().to_string(),
+                        name: clean_cpu_name(cpu.brand()),
+                        cores: 0,
+                        physical_cores: None,
+                        frequency_current: 0,
+                        frequency_base: None,
+                        frequency_min: None,
+                        frequency_max: None,
+                        usage: 0.0,
+                        power_draw: None,
+                    },
+                    0,
+                    0,
+                ));
+                groups.len() - 1
+            }
+        };
+
+        let (entry, frequency_sum, reporting) = &mut groups[index];
+        entry.cores += 1;
+        entry.usage += cpu.cpu_usage();
+        // Cores often report 0 MHz until their first refresh
+        if cpu.frequency() > 0 {
+            *frequency_sum += cpu.frequency();
+            *reporting += 1;
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(mut cpu, frequency_sum, reporting)| {
+            cpu.usage /= cpu.cores as f32;
+            cpu.frequency_current = frequency_sum.checked_div(reporting).unwrap_or(0);
+            cpu
+        })
+        .collect()
+}
+
+/// Fill in the average usage of each CPU model from a refreshed `System`
+pub(crate) fn apply_cpu_usage(cpus: &mut [CpuInfo], sys: &System) {
+    for cpu in cpus {
+        let usages: Vec<f32> = sys
+            .cpus()
+            .iter()
+            .filter(|core| core.brand() == cpu.model)
+            .map(|core| core.cpu_usage())
+            .collect();
+        if !usages.is_empty() {
+            cpu.usage = usages.iter().sum::<f32>() / usages.len() as f32;
+        }
+    }
+}
+
+/// Count the CPUs in a `/sys/devices/system/cpu` list such as `online` (Linux only)
+pub(crate) fn read_cpu_list(name: &str) -> Option<usize> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    fs::read_to_string(format!("/sys/devices/system/cpu/{name}"))
+        .ok()
+        .and_then(|content| parse_cpu_list(&content))
+}
+
+/// Count the CPUs in a kernel range list such as "0-13,15"
+fn parse_cpu_list(content: &str) -> Option<usize> {
+    content
+        .trim()
+        .split(',')
+        .map(|range| match range.split_once('-') {
+            Some((start, end)) => {
+                let start: usize = start.parse().ok()?;
+                let end: usize = end.parse().ok()?;
+                end.checked_sub(start).map(|span| span + 1)
+            }
+            None => range.parse::<usize>().ok().map(|_| 1),
+        })
+        .sum()
+}
+
+/// Fill in the base clock from cpufreq (Linux, single CPU model) or the
+/// "@ 3.40GHz" suffix many brand strings carry
+pub(crate) fn apply_base_frequency(cpus: &mut [CpuInfo]) {
+    let sysfs_base = if cfg!(target_os = "linux") && cpus.len() == 1 {
+        read_u64(Path::new(
+            "/sys/devices/system/cpu/cpu0/cpufreq/base_frequency",
+        ))
+        .map(|khz| khz / 1000)
+    } else {
+        None
+    };
+
+    for cpu in cpus {
+        cpu.frequency_base = sysfs_base.or_else(|| parse_brand_frequency(&cpu.model));
+    }
+}
+
+/// Fill in the supported clock range from cpufreq's `cpuinfo_min_freq` and
+/// `cpuinfo_max_freq`, widest over the cores of each model (Linux only)
+pub(crate) fn apply_frequency_range(cpus: &mut [CpuInfo], sys: &System) {
+    if !cfg!(target_os = "linux") {
+        return;
+    }
+
+    for (index, core) in sys.cpus().iter().enumerate() {
+        let Some(cpu) = cpus.iter_mut().find(|cpu| cpu.model == core.brand()) else {
+            continue;
+        };
+        let cpufreq = PathBuf::from(format!("/sys/devices/system/cpu/cpu{index}/cpufreq"));
+        if let Some(min) = read_u64(&cpufreq.join("cpuinfo_min_freq")).map(|khz| khz / 1000) {
+            cpu.frequency_min = Some(cpu.frequency_min.map_or(min, |old| old.min(min)));
+        }
+        if let Some(max) = read_u64(&cpufreq.join("cpuinfo_max_freq")).map(|khz| khz / 1000) {
+            cpu.frequency_max = Some(cpu.frequency_max.map_or(max, |old| old.max(max)));
+        }
+    }
+}
+
+/// Strip the noise from a brand string, turning
+/// "Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz" into "Intel Core i7-9750H" and
+/// "AMD Ryzen 7 7800X3D 8-Core Processor" into "AMD Ryzen 7 7800X3D"
+fn clean_cpu_name(brand: &str) -> String {
+    let brand = match brand.split_once('@') {
+        Some((name, _)) => name,
+        None => brand,
+    };
+    let mut words: Vec<String> = brand
+        .split_whitespace()
+        .map(|word| {
+            ["(R)", "(r)", "(TM)", "(tm)", "®", "™"]
+                .iter()
+                .fold(word.to_string(), |word, mark| word.replace(mark, ""))
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    // Trailing words that repeat what the rest of the line already says
+    while words
+        .last()
+        .is_some_and(|word| word == "CPU" || word == "Processor" || word.ends_with("-Core"))
+    {
+        words.pop();
+    }
+
+    if words.is_empty() {
+        brand.trim().to_string()
+    } else {
+        words.join(" ")
+    }
+}
+
+/// Parse the nominal clock from a brand string such as
+/// "Intel(R) Core(TM) i7-8700 CPU @ 3.20GHz", in MHz
+fn parse_brand_frequency(brand: &str) -> Option<u64> {
+    let (_, suffix) = brand.rsplit_once('@')?;
+    let ghz: f64 = suffix.trim().strip_suffix("GHz")?.trim().parse().ok()?;
+    Some((ghz * 1000.0).round() as u64)
+}
+
+/// Average CPU package power over a short interval from the RAPL energy
+/// counters in `/sys/class/powercap` (Linux only, usually root-only)
+fn get_cpu_power_draw() -> Option<f32> {
+    const INTERVAL: Duration = Duration::from_millis(250);
+
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // Top-level `intel-rapl:N` zones are packages; `intel-rapl:N:M` are subzones
+    let packages: Vec<PathBuf> = fs::read_dir("/sys/class/powercap")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("intel-rapl:"))
+                .is_some_and(|index| !index.contains(':'))
+        })
+        .collect();
+
+    let read_energy = || -> Option<Vec<u64>> {
+        packages
+            .iter()
+            .map(|path| read_u64(&path.join("energy_uj")))
+            .collect()
+    };
+
+    let before = read_energy().filter(|energy| !energy.is_empty())?;
+    let start = Instant::now();
+    thread::sleep(INTERVAL);
+    let after = read_energy()?;
+    let elapsed = start.elapsed().as_secs_f32();
+
+    let microjoules: u64 = packages
+        .iter()
+        .zip(before.iter().zip(&after))
+        .map(|(path, (&before, &after))| {
+            let max = read_u64(&path.join("max_energy_range_uj")).unwrap_or(u64::MAX);
+            rapl_delta(before, after, max)
+        })
+        .sum();
+
+    Some(microjoules as f32 / 1_000_000.0 / elapsed)
+}
+
+/// Energy consumed between two RAPL readings, allowing for one counter wrap
+fn rapl_delta(before: u64, after: u64, max: u64) -> u64 {
+    if after >= before {
+        after - before
+    } else {
+        max.saturating_sub(before) + after
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Whether a GPU entry is an NVIDIA board
+fn is_nvidia(gpu: &GpuInfo) -> bool {
+    gpu.vendor.to_lowercase().contains("nvidia") || gpu.name.to_lowercase().contains("nvidia")
+}
+
+/// Fill in the power draw of NVIDIA GPUs from `nvidia-smi`, in listing order
+pub(crate) fn apply_nvidia_power_draw(gpus: &mut [GpuInfo], no_exec: bool) {
+    if !gpus.iter().any(is_nvidia) {
+        return;
+    }
+
+    let Some(output) = run_command(
+        no_exec,
+        Command::new("nvidia-smi")
+            .args(["--query-gpu=power.draw", "--format=csv,noheader,nounits"]),
+    ) else {
+        return;
+    };
+
+    // Unsupported boards print "[N/A]", which keeps their slot as `None`
+    let readings = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().parse::<f32>().ok())
+        .collect::<Vec<_>>();
+    for (gpu, watts) in gpus.iter_mut().filter(|gpu| is_nvidia(gpu)).zip(readings) {
+        gpu.power_draw = watts;
+    }
+}
+
+/// Fill in VRAM and utilization of NVIDIA GPUs from `nvidia-smi`, in
+/// listing order; a missing `nvidia-smi` leaves them unknown
+pub(crate) fn apply_nvidia_usage(gpus: &mut [GpuInfo], no_exec: bool) {
+    if !gpus.iter().any(is_nvidia) {
+        return;
+    }
+
+    let Some(output) = run_command(
+        no_exec,
+        Command::new("nvidia-smi").args([
+            "--query-gpu=memory.total,memory.used,utilization.gpu,driver_version",
+            "--format=csv,noheader,nounits",
+        ]),
+    ) else {
+        return;
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for (gpu, line) in gpus
+        .iter_mut()
+        .filter(|gpu| is_nvidia(gpu))
+        .zip(stdout.lines())
+    {
+        // e.g. "8192, 4096, 30, 550.90.07", memory in MiB; "[N/A]" stays `None`
+        let mut values = line.split(',').map(str::trim);
+        let mib = |value: Option<&str>| value?.parse::<u64>().ok().map(|mib| mib * 1024 * 1024);
+        gpu.vram_total = mib(values.next());
+        gpu.vram_used = mib(values.next());
+        gpu.utilization = values.next().and_then(|value| value.parse().ok());
+        gpu.driver_version = values
+            .next()
+            .filter(|version| !version.is_empty() && !version.starts_with('['))
+            .map(str::to_string);
+    }
+}
+
+/// Fill in VRAM and utilization of AMD GPUs from the amdgpu sysfs files,
+/// matching DRM cards to the listed GPUs in order (Linux only)
+pub(crate) fn apply_amd_usage(gpus: &mut [GpuInfo]) {
+    let is_amd = |gpu: &GpuInfo| {
+        let name = format!("{} {}", gpu.vendor, gpu.name).to_lowercase();
+        name.contains("amd") || name.contains("radeon")
+    };
+    if !cfg!(target_os = "linux") || !gpus.iter().any(is_amd) {
+        return;
+    }
+
+    let Ok(entries) = fs::read_dir("/sys/class/drm") else {
+        return;
+    };
+    // Only `cardN`, not its connectors such as `card0-DP-1`
+    let mut cards: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix("card"))
+                .is_some_and(|index| index.chars().all(|c| c.is_ascii_digit()))
+        })
+        .map(|path| path.join("device"))
+        .filter(|device| {
+            fs::read_to_string(device.join("vendor")).is_ok_and(|vendor| vendor.trim() == "0x1002")
+        })
+        .collect();
+    cards.sort();
+
+    for (gpu, device) in gpus.iter_mut().filter(|gpu| is_amd(gpu)).zip(cards) {
+        gpu.vram_total = read_u64(&device.join("mem_info_vram_total"));
+        gpu.vram_used = read_u64(&device.join("mem_info_vram_used"));
+        gpu.utilization = read_u64(&device.join("gpu_busy_percent")).map(|busy| busy as f32);
+    }
+}
+
+/// Filesystems backed by memory or images rather than real storage
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "tmpfs", "devtmpfs", "ramfs", "squashfs", "overlay", "proc", "sysfs", "efivarfs",
+];
+
+/// Collect mounted filesystems, skipping pseudo-filesystems and loop images
+/// and listing bind mounts of the same device only once
+fn collect_disk_info() -> Vec<DiskInfo> {
+    let disks = Disks::new_with_refreshed_list();
+    let mut disks: Vec<_> = disks
+        .list()
+        .iter()
+        .filter(|disk| {
+            let fs_type = disk.file_system().to_string_lossy();
+            let device = disk.name().to_string_lossy();
+            !PSEUDO_FILESYSTEMS.contains(&fs_type.as_ref()) && !device.starts_with("/dev/loop")
+        })
+        .collect();
+
+    // The shortest mount point of a device is the one it is known by,
+    // the others are bind mounts into it. Only device paths identify a
+    // device; on Windows the name is the volume label, which drives share.
+    disks.sort_by_key(|disk| disk.mount_point().as_os_str().len());
+    let mut seen = HashSet::new();
+    disks
+        .into_iter()
+        .filter(|disk| {
+            let name = disk.name();
+            if name.to_string_lossy().starts_with("/dev/") {
+                seen.insert(name)
+            } else {
+                seen.insert(disk.mount_point().as_os_str())
+            }
+        })
+        .map(|disk| {
+            let total = disk.total_space();
+            DiskInfo {
+                mount_point: disk.mount_point().to_string_lossy().into_owned(),
+                fs_type: disk.file_system().to_string_lossy().into_owned(),
+                total,
+                used: total.saturating_sub(disk.available_space()),
+            }
+        })
+        .collect()
+}
+
+/// Order disks for display
+fn sort_disks(disks: &mut [DiskInfo], sort: DiskSort) {
+    match sort {
+        DiskSort::Mount => disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point)),
+        // Fullest first
+        DiskSort::Usage => disks.sort_by(|a, b| {
+            percentage(b.used, b.total)
+                .total_cmp(&percentage(a.used, a.total))
+                .then_with(|| a.mount_point.cmp(&b.mount_point))
+        }),
+        // Largest first
+        DiskSort::Size => disks.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.mount_point.cmp(&b.mount_point))
+        }),
+    }
+}
+
+/// Get GPU information list
+pub(crate) fn get_gpu_info_list(no_exec: bool) -> Vec<GpuInfo> {
+    if no_exec {
+        vec![GpuInfo::unknown()]
+    } else if cfg!(target_os = "windows") {
+        get_gpu_info_windows_list()
+    } else if cfg!(target_os = "linux") {
+        get_gpu_info_linux_list()
+    } else if cfg!(target_os = "macos") {
+        get_gpu_info_macos_list()
+    } else {
+        vec![GpuInfo::unknown()]
+    }
+}
+
+/// Get GPU information on Windows system (multiple GPUs)
+fn get_gpu_info_windows_list() -> Vec<GpuInfo> {
+    let mut gpus: Vec<GpuInfo> = cim_instances(
+        false,
+        "Win32_VideoController",
+        None,
+        &["Name", "AdapterCompatibility", "DriverVersion"],
+    )
+    .iter()
+    .filter_map(|controller| {
+        let name = cim_string(controller, "Name")?;
+        Some(GpuInfo {
+            name,
+            vendor: cim_string(controller, "AdapterCompatibility").unwrap_or_default(),
+            power_draw: None,
+            vram_total: None,
+            vram_used: None,
+            utilization: None,
+            metal: None,
+            driver_version: cim_string(controller, "DriverVersion"),
+        })
+    })
+    .collect();
+
+    if gpus.is_empty() {
+        gpus.push(GpuInfo::unknown());
+    }
+
+    gpus
+}
+
+/// Query CIM instances through PowerShell (`wmic` is gone from recent
+/// Windows 11 builds), returning the selected properties of each instance
+fn cim_instances(
+    no_exec: bool,
+    class: &str,
+    filter: Option<&str>,
+    properties: &[&str],
+) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    let filter = filter
+        .map(|filter| format!(" -Filter '{filter}'"))
+        .unwrap_or_default();
+    let script = format!(
+        "Get-CimInstance -ClassName {class}{filter} | Select-Object {} | ConvertTo-Json -Compress",
+        properties.join(",")
+    );
+    let Some(output) = run_command(
+        no_exec,
+        Command::new("powershell").args(["-NoProfile", "-NonInteractive", "-Command", &script]),
+    ) else {
+        return Vec::new();
+    };
+    parse_cim_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `ConvertTo-Json` output, which is a bare object for a single
+/// instance and an array otherwise
+fn parse_cim_json(output: &str) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    match serde_json::from_str(output.trim()) {
+        Ok(serde_json::Value::Array(instances)) => instances
+            .into_iter()
+            .filter_map(|instance| match instance {
+                serde_json::Value::Object(instance) => Some(instance),
+                _ => None,
+            })
+            .collect(),
+        Ok(serde_json::Value::Object(instance)) => vec![instance],
+        _ => Vec::new(),
+    }
+}
+
+/// A non-empty string property of a CIM instance
+fn cim_string(instance: &serde_json::Map<String, serde_json::Value>, key: &str) -> Option<String> {
+    let value = instance.get(key)?.as_str()?.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// A numeric property of a CIM instance
+fn cim_u64(instance: &serde_json::Map<String, serde_json::Value>, key: &str) -> Option<u64> {
+    instance.get(key)?.as_u64()
+}
+
+/// Get GPU information on Linux system (multiple GPUs)
+fn get_gpu_info_linux_list() -> Vec<GpuInfo> {
+    let mut gpus = run_command(false, Command::new("lspci").args(["-mm"]))
+        .map(|output| parse_lspci(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default();
+
+    if gpus.is_empty() {
+        gpus.push(GpuInfo::unknown());
+    }
+
+    gpus
+}
+
+/// Display controllers in `lspci -mm` output, whose lines quote the class,
+/// vendor and device, e.g.
+/// `01:00.0 "VGA compatible controller" "NVIDIA Corporation" "AD104 [GeForce RTX 4070]" ...`
+fn parse_lspci(output: &str) -> Vec<GpuInfo> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('"').collect();
+            if parts.len() < 6 || !matches!(parts[1], "VGA compatible controller" | "3D controller")
+            {
+                return None;
+            }
+            Some(GpuInfo {
+                name: format!("{} {}", parts[3], parts[5]),
+                vendor: parts[3].to_string(),
+                power_draw: None,
+                vram_total: None,
+                vram_used: None,
+                utilization: None,
+                metal: None,
+                driver_version: None,
+            })
+        })
+        .collect()
+}
+
+/// Output of `system_profiler SPDisplaysDataType -json`, which lists both
+/// GPUs and the displays attached to them; run once as it takes a while
+fn system_profiler_displays(no_exec: bool) -> Option<&'static str> {
+    static OUTPUT: OnceLock<Option<String>> = OnceLock::new();
+    OUTPUT
+        .get_or_init(|| {
+            let output = run_command(
+                no_exec,
+                Command::new("system_profiler").args(["SPDisplaysDataType", "-json"]),
+            )?;
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+        .as_deref()
+}
+
+/// Get GPU information on macOS system (multiple GPUs)
+fn get_gpu_info_macos_list() -> Vec<GpuInfo> {
+    let mut gpus = system_profiler_displays(false)
+        .map(parse_macos_gpus)
+        .unwrap_or_default();
+
+    if gpus.is_empty() {
+        gpus.push(GpuInfo::unknown());
+    }
+
+    gpus
+}
+
+/// `system_profiler SPDisplaysDataType -json` output
+#[derive(Debug, Deserialize)]
+struct SpDisplaysData {
+    #[serde(rename = "SPDisplaysDataType", default)]
+    gpus: Vec<SpDisplaysGpu>,
+}
+
+/// One graphics adapter; its attached displays are left to
+/// `parse_macos_displays`
+#[derive(Debug, Deserialize)]
+struct SpDisplaysGpu {
+    #[serde(rename = "sppci_model")]
+    model: Option<String>,
+    #[serde(rename = "_name")]
+    name: Option<String>,
+    /// e.g. "sppci_vendor_Apple" or "NVIDIA (0x10de)"
+    #[serde(rename = "spdisplays_vendor")]
+    vendor: Option<String>,
+    /// e.g. "1536 MB"; Apple silicon shares system memory and has none
+    #[serde(rename = "spdisplays_vram")]
+    vram: Option<String>,
+    #[serde(rename = "spdisplays_vram_shared")]
+    vram_shared: Option<String>,
+    /// e.g. "spdisplays_metal3"
+    #[serde(rename = "spdisplays_mtlgpufamilysupport")]
+    metal_family: Option<String>,
+    /// Older releases, e.g. "spdisplays_metal_family2"
+    #[serde(rename = "spdisplays_metal")]
+    metal: Option<String>,
+}
+
+/// Parse the GPUs listed by `system_profiler SPDisplaysDataType -json`
+fn parse_macos_gpus(output: &str) -> Vec<GpuInfo> {
+    let Ok(data) = serde_json::from_str::<SpDisplaysData>(output) else {
+        return Vec::new();
+    };
+
+    data.gpus
+        .into_iter()
+        .filter_map(|gpu| {
+            let name = gpu.model.or(gpu.name)?;
+            let vendor = gpu
+                .vendor
+                .as_deref()
+                .map(macos_gpu_vendor)
+                .filter(|vendor| !vendor.is_empty())
+                .unwrap_or_else(|| infer_gpu_vendor(&name).to_string());
+            let vram_total = gpu
+                .vram
+                .or(gpu.vram_shared)
+                .as_deref()
+                .and_then(parse_macos_vram);
+            let metal = gpu
+                .metal_family
+                .or(gpu.metal)
+                .as_deref()
+                .and_then(metal_family_name);
+            Some(GpuInfo {
+                name,
+                vendor,
+                power_draw: None,
+                vram_total,
+                vram_used: None,
+                utilization: None,
+                metal,
+                // Part of the OS release on macOS
+                driver_version: None,
+            })
+        })
+        .collect()
+}
+
+/// Strip the localization key prefix and PCI vendor ID suffix from a
+/// `spdisplays_vendor` value, spelling known vendors the usual way
+/// ("sppci_vendor_amd" is "AMD")
+fn macos_gpu_vendor(vendor: &str) -> String {
+    let vendor = vendor.strip_prefix("sppci_vendor_").unwrap_or(vendor);
+    let vendor = match vendor.split_once(" (0x") {
+        Some((name, _)) => name,
+        None => vendor,
+    };
+    match infer_gpu_vendor(vendor) {
+        "Unknown" => vendor.trim().to_string(),
+        known => known.to_string(),
+    }
+}
+
+/// Guess the vendor from the model name
+fn infer_gpu_vendor(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    if name.contains("nvidia") {
+        "NVIDIA"
+    } else if name.contains("amd") || name.contains("radeon") {
+        "AMD"
+    } else if name.contains("intel") {
+        "Intel"
+    } else if name.contains("apple") {
+        "Apple"
+    } else {
+        "Unknown"
+    }
+}
+
+/// Parse a VRAM size such as "1536 MB" or "4 GB" into bytes
+fn parse_macos_vram(vram: &str) -> Option<u64> {
+    let (amount, unit) = vram.trim().split_once(' ')?;
+    let amount: u64 = amount.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some(amount * multiplier)
+}
+
+/// Turn "spdisplays_metal3" or "spdisplays_metal_family2" into "Metal 3"
+/// or "Metal 2"
+fn metal_family_name(family: &str) -> Option<String> {
+    let family = family.strip_prefix("spdisplays_")?.strip_prefix("metal")?;
+    let version = family.trim_start_matches("_family");
+    if version.is_empty() {
+        Some("Metal".to_string())
+    } else if version.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("Metal {version}"))
+    } else {
+        None
+    }
+}
+
+/// Get attached displays, with the primary display first
+fn get_display_info_list(no_exec: bool) -> Vec<DisplayInfo> {
+    let mut displays = if cfg!(target_os = "linux") {
+        get_display_info_linux(no_exec)
+    } else if cfg!(target_os = "macos") {
+        system_profiler_displays(no_exec)
+            .map(parse_macos_displays)
+            .unwrap_or_default()
+    } else if cfg!(target_os = "windows") {
+        get_display_info_windows(no_exec)
+    } else {
+        Vec::new()
+    };
+
+    if let Some(primary) = displays.first_mut() {
+        primary.scale_factor = get_scale_factor(no_exec);
+    }
+    displays
+}
+
+/// Ask the compositor through `wlr-randr` on Wayland, then X11's `xrandr`
+fn get_display_info_linux(no_exec: bool) -> Vec<DisplayInfo> {
+    if env::var_os("WAYLAND_DISPLAY").is_some() {
+        let displays = run_command(no_exec, &mut Command::new("wlr-randr"))
+            .map(|output| parse_wlr_randr(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default();
+        if !displays.is_empty() {
+            return displays;
+        }
+    }
+    run_command(no_exec, Command::new("xrandr").arg("--current"))
+        .map(|output| parse_xrandr(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse the current mode of each enabled output from `wlr-randr`, whose
+/// mode lines look like "    2560x1440 px, 143.912003 Hz (preferred, current)"
+fn parse_wlr_randr(output: &str) -> Vec<DisplayInfo> {
+    let mut displays = Vec::new();
+    let mut enabled = true;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !line.starts_with(' ') {
+            // A new output; enabled unless told otherwise
+            enabled = true;
+            continue;
+        }
+        if let Some(state) = trimmed.strip_prefix("Enabled:") {
+            enabled = state.trim() == "yes";
+            continue;
+        }
+        if !enabled || !trimmed.ends_with("current)") {
+            continue;
+        }
+
+        let Some((mode, rest)) = trimmed.split_once(" px,") else {
+            continue;
+        };
+        let Some((width, height)) = mode.split_once('x') else {
+            continue;
+        };
+        let (Ok(width), Ok(height)) = (width.parse(), height.parse()) else {
+            continue;
+        };
+        let refresh_hz = rest
+            .split_whitespace()
+            .next()
+            .and_then(|rate| rate.parse().ok());
+        displays.push(DisplayInfo {
+            width,
+            height,
+            refresh_hz,
+            scale_factor: None,
+        });
+    }
+    displays
+}
+
+/// Parse the displays attached to each GPU from `system_profiler -json`,
+/// e.g. `"_spdisplays_resolution" : "2560 x 1440 @ 60.00Hz"`
+fn parse_macos_displays(output: &str) -> Vec<DisplayInfo> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(output) else {
+        return Vec::new();
+    };
+    let gpus = json["SPDisplaysDataType"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut displays: Vec<(bool, DisplayInfo)> = gpus
+        .iter()
+        .flat_map(|gpu| {
+            gpu["spdisplays_ndrvs"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter_map(|display| {
+            let resolution = display["_spdisplays_resolution"].as_str()?;
+            let (size, rate) = match resolution.split_once('@') {
+                Some((size, rate)) => (size, Some(rate)),
+                None => (resolution, None),
+            };
+            let (width, height) = size.split_once(" x ")?;
+            let height = height.split_whitespace().next()?;
+            let info = DisplayInfo {
+                width: width.trim().parse().ok()?,
+                height: height.parse().ok()?,
+                refresh_hz: rate.and_then(|rate| rate.trim().trim_end_matches("Hz").parse().ok()),
+                scale_factor: None,
+            };
+            let main = display["spdisplays_main"].as_str() == Some("spdisplays_yes");
+            Some((main, info))
+        })
+        .collect();
+
+    // Main display first, as with xrandr's primary output
+    displays.sort_by_key(|(main, _)| !main);
+    displays.into_iter().map(|(_, info)| info).collect()
+}
+
+/// Current mode of each video controller with a display attached
+fn get_display_info_windows(no_exec: bool) -> Vec<DisplayInfo> {
+    cim_instances(
+        no_exec,
+        "Win32_VideoController",
+        None,
+        &[
+            "CurrentHorizontalResolution",
+            "CurrentVerticalResolution",
+            "CurrentRefreshRate",
+        ],
+    )
+    .iter()
+    // Controllers without an attached display report null values
+    .filter_map(|controller| {
+        Some(DisplayInfo {
+            width: cim_u64(controller, "CurrentHorizontalResolution")? as u32,
+            height: cim_u64(controller, "CurrentVerticalResolution")? as u32,
+            refresh_hz: cim_u64(controller, "CurrentRefreshRate").map(|hz| hz as f32),
+            scale_factor: None,
+        })
+    })
+    .collect()
+}
+
+/// Parse connected outputs and their active modes from `xrandr`
+fn parse_xrandr(output: &str) -> Vec<DisplayInfo> {
+    let mut displays = Vec::new();
+    let mut primary = None;
+    let mut connected = false;
+    let mut is_primary = false;
+
+    for line in output.lines() {
+        if !line.starts_with(' ') {
+            connected = line.contains(" connected");
+            is_primary = line.contains(" primary ");
+            continue;
+        }
+
+        // Mode lines look like "   2560x1440    143.91*+  59.95"
+        if !connected || !line.contains('*') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let Some((width, height)) = fields.next().and_then(|mode| mode.split_once('x')) else {
+            continue;
+        };
+        let (Ok(width), Ok(height)) = (width.parse(), height.trim_end_matches('i').parse()) else {
+            continue;
+        };
+        let refresh_hz = fields
+            .find(|rate| rate.contains('*'))
+            .and_then(|rate| rate.trim_end_matches(['*', '+']).parse().ok());
+
+        // Only an output with an active mode can be the primary display
+        if is_primary {
+            primary = Some(displays.len());
+        }
+        displays.push(DisplayInfo {
+            width,
+            height,
+            refresh_hz,
+            scale_factor: None,
+        });
+        connected = false;
+    }
+
+    if let Some(primary) = primary {
+        let display = displays.remove(primary);
+        displays.insert(0, display);
+    }
+    displays
+}
+
+/// Get the desktop scale factor of the primary display
+fn get_scale_factor(no_exec: bool) -> Option<f32> {
+    if cfg!(target_os = "linux") {
+        get_scale_factor_linux(no_exec)
+    } else if cfg!(target_os = "windows") {
+        // AppliedDPI is relative to the 96 DPI baseline, e.g. "0x90" for 150%
+        let output = run_command(
+            no_exec,
+            Command::new("reg").args([
+                "query",
+                "HKCU\\Control Panel\\Desktop\\WindowMetrics",
+                "/v",
+                "AppliedDPI",
+            ]),
+        )?;
+        let dpi = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim_start().starts_with("AppliedDPI"))?
+            .split_whitespace()
+            .nth(2)?
+            .trim_start_matches("0x")
+            .to_string();
+        u32::from_str_radix(&dpi, 16)
+            .ok()
+            .map(|dpi| dpi as f32 / 96.0)
+    } else if cfg!(target_os = "macos") {
+        let output = run_command(
+            no_exec,
+            Command::new("system_profiler").arg("SPDisplaysDataType"),
+        )?;
+        parse_macos_scale(&String::from_utf8_lossy(&output.stdout))
+    } else {
+        None
+    }
+}
+
+/// Read the scale factor from the toolkit environment, KDE or GNOME settings
+fn get_scale_factor_linux(no_exec: bool) -> Option<f32> {
+    let valid = |scale: f32| scale > 0.0;
+
+    if let Some(scale) = ["GDK_SCALE", "QT_SCALE_FACTOR"]
+        .iter()
+        .filter_map(|var| env::var(var).ok()?.trim().parse().ok())
+        .find(|&scale| valid(scale))
+    {
+        return Some(scale);
+    }
+
+    // KDE keeps it in kdeglobals under [KScreen]
+    let kde_scale = env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".config/kdeglobals"))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| {
+            content
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("ScaleFactor="))
+                .and_then(|scale| scale.trim().parse().ok())
+        })
+        .filter(|&scale| valid(scale));
+    if kde_scale.is_some() {
+        return kde_scale;
+    }
+
+    // GNOME prints e.g. "uint32 2"; 0 means automatic
+    let output = run_command(
+        no_exec,
+        Command::new("gsettings").args(["get", "org.gnome.desktop.interface", "scaling-factor"]),
+    )?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .last()?
+        .parse()
+        .ok()
+        .filter(|&scale| valid(scale))
+}
+
+/// Derive the scale from the native and "looks like" resolutions on macOS
+fn parse_macos_scale(output: &str) -> Option<f32> {
+    let width_after = |prefix: &str| -> Option<f32> {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(prefix))?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    };
+
+    let native = width_after("Resolution:")?;
+    let looks_like = width_after("UI Looks like:")?;
+    (looks_like > 0.0).then(|| native / looks_like)
+}
+
+thread_local! {
+    /// Commands that could not be run by the probe in progress
+    static COMMAND_FAILURES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Run one probe and, when `found` says it came up empty, record the
+/// commands it could not run as "<name> probe failed: <reason>". Missing
+/// tools are expected along fallback chains, so they only count when
+/// nothing else worked either
+fn probe<T>(
+    errors: &mut Vec<String>,
+    name: &str,
+    run: impl FnOnce() -> T,
+    found: impl FnOnce(&T) -> bool,
+) -> T {
+    COMMAND_FAILURES.with_borrow_mut(Vec::clear);
+    let value = run();
+    let failures = COMMAND_FAILURES.take();
+    if !found(&value) {
+        errors.extend(
+            failures
+                .into_iter()
+                .map(|failure| format!("{name} probe failed: {failure}")),
+        );
+    }
+    value
+}
+
+/// A `probe` running on a thread of its own
+struct PendingProbe<T> {
+    name: &'static str,
+    handle: JoinHandle<(T, Vec<String>)>,
+}
+
+/// Start a `probe` on its own thread, for probes that mostly wait on
+/// external commands
+fn spawn_probe<T: Send + 'static>(
+    name: &'static str,
+    run: impl FnOnce() -> T + Send + 'static,
+    found: impl FnOnce(&T) -> bool + Send + 'static,
+) -> PendingProbe<T> {
+    let handle = thread::spawn(move || {
+        let mut errors = Vec::new();
+        let value = probe(&mut errors, name, run, found);
+        (value, errors)
+    });
+    PendingProbe { name, handle }
+}
+
+impl<T> PendingProbe<T> {
+    /// Wait for the probe, adding its failures to `errors`; `None` if it
+    /// panicked
+    fn join(self, errors: &mut Vec<String>) -> Option<T> {
+        match self.handle.join() {
+            Ok((value, failures)) => {
+                errors.extend(failures);
+                Some(value)
+            }
+            Err(_) => {
+                errors.push(format!("{} probe failed: panicked", self.name));
+                None
+            }
+        }
+    }
+}
+
+/// Note a command that could not be started or finished for `probe`
+fn record_failure(command: &Command, reason: &str) {
+    let program = command.get_program().to_string_lossy().into_owned();
+    COMMAND_FAILURES.with_borrow_mut(|failures| failures.push(format!("{program} {reason}")));
+}
+
+/// Run an external command unless shell-outs are disabled
+pub(crate) fn run_command(no_exec: bool, command: &mut Command) -> Option<Output> {
+    if no_exec {
+        return None;
+    }
+    command
+        .output()
+        .map_err(|err| record_failure(command, &spawn_error(&err)))
+        .ok()
+}
+
+/// Why a command could not be started, e.g. "not found"
+fn spawn_error(err: &std::io::Error) -> String {
+    if err.kind() == ErrorKind::NotFound {
+        "not found".to_string()
+    } else {
+        format!("failed: {err}")
+    }
+}
+
+/// Run an external command, killing it if it exceeds the timeout
+fn run_command_with_timeout(command: &mut Command, timeout: Duration) -> Option<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| record_failure(command, &spawn_error(&err)))
+        .ok()?;
+
+    // Drain stdout on a separate thread so a full pipe can't stall the child
+    let mut stdout = child.stdout.take()?;
+    let reader = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stdout.read_to_end(&mut buffer);
+        buffer
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() < timeout => thread::sleep(Duration::from_millis(50)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                record_failure(command, &format!("timed out after {}s", timeout.as_secs()));
+                return None;
+            }
+        }
+    };
+
+    Some(Output {
+        status,
+        stdout: reader.join().ok()?,
+        stderr: Vec::new(),
+    })
+}
+
+/// Count pending package updates on Linux
+fn get_updates_available() -> Option<usize> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    // Arch: exit code 2 means no updates
+    if let Some(output) =
+        run_command_with_timeout(&mut Command::new("checkupdates"), UPDATE_CHECK_TIMEOUT)
+    {
+        return match output.status.code() {
+            Some(0) => Some(count_lines(&output.stdout, |_| true)),
+            Some(2) => Some(0),
+            _ => None,
+        };
+    }
+
+    // Debian/Ubuntu
+    if let Some(output) = run_command_with_timeout(
+        Command::new("apt").args(["list", "--upgradable"]),
+        UPDATE_CHECK_TIMEOUT,
+    ) {
+        if output.status.success() {
+            return Some(count_lines(&output.stdout, |line| {
+                line.contains("[upgradable from")
+            }));
+        }
+        return None;
+    }
+
+    // Fedora: exit code 100 means updates are available
+    if let Some(output) = run_command_with_timeout(
+        Command::new("dnf").args(["check-update", "-q"]),
+        UPDATE_CHECK_TIMEOUT,
+    ) {
+        return match output.status.code() {
+            Some(0) => Some(0),
+            Some(100) => {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                Some(
+                    stdout
+                        .lines()
+                        .take_while(|line| !line.starts_with("Obsoleting"))
+                        .filter(|line| line.split_whitespace().count() == 3)
+                        .count(),
+                )
+            }
+            _ => None,
+        };
+    }
+
+    None
+}
+
+/// Ask a public resolver for our address through `curl`, which ships with
+/// Windows 10 and later as well as macOS and most Linux installs
+fn get_public_ip() -> Option<String> {
+    let output = run_command_with_timeout(
+        Command::new("curl").args([
+            "--silent",
+            "--fail",
+            "--max-time",
+            &PUBLIC_IP_TIMEOUT.as_secs().to_string(),
+            "https://api.ipify.org",
+        ]),
+        PUBLIC_IP_TIMEOUT,
+    )?;
+    if !output.status.success() {
+        return None;
+    }
+    // Anything else (a captive portal page) is not an address
+    let ip = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    ip.parse::<IpAddr>().is_ok().then_some(ip)
+}
+
+/// Count installed packages of every package manager found on this OS.
+/// Each manager is queried on its own, so a missing one is simply skipped.
+fn collect_packages(no_exec: bool) -> Vec<(String, usize)> {
+    let managers: &[(&str, &str, &[&str])] = if cfg!(target_os = "linux") {
+        &[
+            ("dpkg", "dpkg", &["-l"]),
+            ("rpm", "rpm", &["-qa"]),
+            ("pacman", "pacman", &["-Qq"]),
+            ("flatpak", "flatpak", &["list", "--app"]),
+        ]
+    } else if cfg!(target_os = "macos") {
+        &[
+            ("brew", "brew", &["list", "-1"]),
+            ("port", "port", &["installed"]),
+        ]
+    } else if cfg!(target_os = "windows") {
+        // Scoop is a PowerShell shim that only cmd knows how to start
+        &[
+            ("choco", "choco", &["list", "--limit-output"]),
+            ("scoop", "cmd", &["/C", "scoop", "list"]),
+            ("winget", "winget", &["list", "--disable-interactivity"]),
+        ]
+    } else {
+        &[]
+    };
+
+    managers
+        .iter()
+        .filter_map(|&(name, program, args)| {
+            let output = run_command(no_exec, Command::new(program).args(args))?;
+            if !output.status.success() {
+                return None;
+            }
+            let count = count_packages(name, &output.stdout);
+            (count > 0).then(|| (name.to_string(), count))
+        })
+        .collect()
+}
+
+/// Count the packages in a package manager's listing
+fn count_packages(manager: &str, stdout: &[u8]) -> usize {
+    match manager {
+        // Installed packages are marked "ii"; the header and removed ones aren't
+        "dpkg" => count_lines(stdout, |line| line.starts_with("ii ")),
+        // Ports are listed indented below a heading
+        "port" => count_lines(stdout, |line| line.starts_with(char::is_whitespace)),
+        // Tables: count the rows below the dashed separator
+        "scoop" | "winget" => {
+            let text = String::from_utf8_lossy(stdout);
+            text.lines()
+                .skip_while(|line| !line.trim_start().starts_with("--"))
+                .skip(1)
+                .filter(|line| !line.trim().is_empty())
+                .count()
+        }
+        _ => count_lines(stdout, |_| true),
+    }
+}
+
+/// Count non-empty output lines matching a predicate
+fn count_lines(stdout: &[u8], predicate: impl Fn(&str) -> bool) -> usize {
+    String::from_utf8_lossy(stdout)
+        .lines()
+        .filter(|line| !line.trim().is_empty() && predicate(line))
+        .count()
+}
+
+/// Overall compression ratio of the zram devices used as swap on Linux
+fn get_zram_ratio() -> Option<f32> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let swaps = fs::read_to_string("/proc/swaps").ok()?;
+    let (original, compressed) = swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next()?.strip_prefix("/dev/"))
+        .filter(|device| device.starts_with("zram"))
+        .filter_map(|device| fs::read_to_string(format!("/sys/block/{device}/mm_stat")).ok())
+        .filter_map(|content| parse_mm_stat(&content))
+        .fold((0, 0), |(original, compressed), (orig, compr)| {
+            (original + orig, compressed + compr)
+        });
+
+    (compressed > 0).then(|| original as f32 / compressed as f32)
+}
+
+/// Parse `orig_data_size` and `compr_data_size`, the first two of the
+/// space-separated counters in a zram `mm_stat` file; `None` for a device
+/// holding no data yet, which has no ratio
+fn parse_mm_stat(content: &str) -> Option<(u64, u64)> {
+    let mut fields = content.split_whitespace().map(|field| field.parse::<u64>());
+    let original = fields.next()?.ok()?;
+    let compressed = fields.next()?.ok()?;
+    (compressed > 0).then_some((original, compressed))
+}
+
+/// Source address of the default route in the requested family; `Auto`
+/// prefers IPv4 and falls back to IPv6 on IPv6-only networks
+pub(crate) fn default_route_ip(version: IpVersion) -> Result<IpAddr, local_ip_address::Error> {
+    match version {
+        IpVersion::V4 => local_ip(),
+        IpVersion::V6 => local_ipv6(),
+        IpVersion::Auto => local_ip().or_else(|_| local_ipv6()),
+    }
+}
+
+/// Address of the named interface in the requested family; `Auto` prefers
+/// IPv4
+fn interface_ip(interfaces: &[(String, IpAddr)], name: &str, version: IpVersion) -> Option<IpAddr> {
+    let addresses: Vec<IpAddr> = interfaces
+        .iter()
+        .filter(|(interface, _)| interface == name)
+        .map(|&(_, ip)| ip)
+        .collect();
+    match version {
+        IpVersion::V4 => addresses.iter().find(|ip| ip.is_ipv4()),
+        IpVersion::V6 => addresses.iter().find(|ip| ip.is_ipv6()),
+        IpVersion::Auto => addresses
+            .iter()
+            .find(|ip| ip.is_ipv4())
+            .or(addresses.first()),
+    }
+    .copied()
+}
+
+/// Group the addresses by interface, add MAC addresses and mark the
+/// interface holding the default route's source address
+fn collect_interfaces(
+    addresses: &[(String, IpAddr)],
+    default_ip: Option<IpAddr>,
+    include_loopback: bool,
+) -> Vec<NetworkInterface> {
+    let networks = Networks::new_with_refreshed_list();
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+    for (name, ip) in addresses {
+        if ip.is_loopback() && !include_loopback {
+            continue;
+        }
+        let index = match interfaces
+            .iter()
+            .position(|interface| &interface.name == name)
+        {
+            Some(index) => index,
+            None => {
+                let mac = networks
+                    .list()
+                    .get(name)
+                    .map(|data| data.mac_address())
+                    .filter(|mac| !mac.is_unspecified())
+                    .map(|mac| mac.to_string());
+                interfaces.push(NetworkInterface {
+                    name: name.clone(),
+                    mac,
+                    ipv4: Vec::new(),
+                    ipv6: Vec::new(),
+                    default_route: false,
+                    rx_rate: None,
+                    tx_rate: None,
+                });
+                interfaces.len() - 1
+            }
+        };
+
+        let interface = &mut interfaces[index];
+        match ip {
+            IpAddr::V4(_) => interface.ipv4.push(ip.to_string()),
+            IpAddr::V6(_) => interface.ipv6.push(ip.to_string()),
+        }
+        if Some(*ip) == default_ip {
+            interface.default_route = true;
+        }
+    }
+    // Stable, so the rest keep the order the system lists them in
+    interfaces.sort_by_key(|interface| !interface.default_route);
+    interfaces
+}
+
+/// Find the first tunnel interface (WireGuard, OpenVPN tun/tap, macOS utun)
+/// carrying a routable address
+fn detect_vpn_interface(interfaces: &[(String, IpAddr)]) -> Option<String> {
+    const PREFIXES: [&str; 4] = ["tun", "wg", "tap", "utun"];
+
+    // macOS keeps idle utun interfaces with only link-local IPv6 addresses
+    let routable = |ip: &IpAddr| match ip {
+        IpAddr::V4(ip) => !ip.is_loopback() && !ip.is_link_local(),
+        IpAddr::V6(ip) => !ip.is_loopback() && (ip.segments()[0] & 0xffc0) != 0xfe80,
+    };
+
+    interfaces
+        .iter()
+        .find(|(name, ip)| {
+            PREFIXES.iter().any(|prefix| {
+                name.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.chars().next().is_none_or(|c| c.is_ascii_digit()))
+            }) && routable(ip)
+        })
+        .map(|(name, _)| name.clone())
+}
+
+/// Get the default gateway, preferring IPv4
+fn get_default_gateway(no_exec: bool) -> Option<String> {
+    if cfg!(target_os = "linux") {
+        fs::read_to_string("/proc/net/route")
+            .ok()
+            .and_then(|content| parse_proc_route(&content))
+            .or_else(|| {
+                fs::read_to_string("/proc/net/ipv6_route")
+                    .ok()
+                    .and_then(|content| parse_proc_ipv6_route(&content))
+            })
+    } else if cfg!(target_os = "windows") {
+        let output = run_command(no_exec, Command::new("ipconfig").arg("/all"))?;
+        parse_ipconfig(&String::from_utf8_lossy(&output.stdout)).0
+    } else if cfg!(target_os = "macos") {
+        let output = run_command(
+            no_exec,
+            Command::new("route").args(["-n", "get", "default"]),
+        )?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("gateway:"))
+            .map(|gateway| gateway.trim().to_string())
+    } else {
+        None
+    }
+}
+
+/// Get the configured DNS servers
+fn get_dns_servers(no_exec: bool) -> Vec<String> {
+    if cfg!(target_os = "linux") {
+        fs::read_to_string("/etc/resolv.conf")
+            .map(|content| parse_resolv_conf(&content))
+            .unwrap_or_default()
+    } else if cfg!(target_os = "windows") {
+        run_command(no_exec, Command::new("ipconfig").arg("/all"))
+            .map(|output| parse_ipconfig(&String::from_utf8_lossy(&output.stdout)).1)
+            .unwrap_or_default()
+    } else if cfg!(target_os = "macos") {
+        run_command(no_exec, Command::new("scutil").arg("--dns"))
+            .map(|output| parse_scutil_dns(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Find the IPv4 default gateway in `/proc/net/route`
+fn parse_proc_route(content: &str) -> Option<String> {
+    content.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 3 || fields[1] != "00000000" {
+            return None;
+        }
+        // The gateway is a little-endian hex u32
+        let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+        (gateway != 0).then(|| Ipv4Addr::from(gateway.to_le_bytes()).to_string())
+    })
+}
+
+/// Find the IPv6 default gateway in `/proc/net/ipv6_route`
+fn parse_proc_ipv6_route(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 5 || !fields[0].trim_matches('0').is_empty() || fields[1] != "00" {
+            return None;
+        }
+        let next_hop = u128::from_str_radix(fields[4], 16).ok()?;
+        (next_hop != 0).then(|| Ipv6Addr::from(next_hop).to_string())
+    })
+}
+
+/// Collect `nameserver` entries from `resolv.conf`
+fn parse_resolv_conf(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .map(|server| server.trim().to_string())
+        .filter(|server| !server.is_empty())
+        .collect()
+}
 
-        // CPU information
-        let cpus = collect_cpu_info(&sys);
+/// Extract the default gateway and DNS servers from `ipconfig /all`
+fn parse_ipconfig(output: &str) -> (Option<String>, Vec<String>) {
+    let mut gateway = None;
+    let mut dns_servers = Vec::new();
+    let mut in_dns = false;
 
-        // Memory information
-        let memory_total = sys.total_memory();
-        let memory_used = sys.used_memory();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        // Continuation lines carry no label, only a value
+        let (label, value) = match trimmed.split_once(" : ") {
+            Some((label, value)) => (Some(label), value.trim()),
+            None => (None, trimmed),
+        };
 
-        // GPU information
-        let gpus = get_gpu_info_list();
+        match label {
+            Some(label) => {
+                in_dns = label.starts_with("DNS Servers");
+                if label.starts_with("Default Gateway") && gateway.is_none() && !value.is_empty() {
+                    gateway = Some(value.to_string());
+                } else if in_dns && !value.is_empty() {
+                    dns_servers.push(value.to_string());
+                }
+            }
+            None if in_dns && value.parse::<IpAddr>().is_ok() => {
+                dns_servers.push(value.to_string());
+            }
+            None => in_dns = false,
+        }
+    }
 
-        // Local IP address
-        let local_ip = get_local_ip();
+    (gateway, dns_servers)
+}
 
-        // Shell and Terminal information
-        let shell = get_shell_info();
-        let terminal = get_terminal_info();
+/// Collect unique nameservers from `scutil --dns`
+fn parse_scutil_dns(output: &str) -> Vec<String> {
+    let mut servers: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if let Some((key, value)) = line.trim().split_once(" : ") {
+            let value = value.trim().to_string();
+            if key.starts_with("nameserver[") && !servers.contains(&value) {
+                servers.push(value);
+            }
+        }
+    }
+    servers
+}
 
-        Ok(Self {
-            os_name,
-            os_version,
-            os_arch,
-            kernel_version,
-            hostname,
-            username,
-            uptime,
-            cpus,
-            memory_total,
-            memory_used,
-            gpus,
-            local_ip,
-            shell,
-            terminal,
-        })
+/// Get the character locale: the first of `LC_ALL`, `LC_CTYPE` and `LANG`
+/// that is set, as the C library picks it, or the user's culture on Windows
+fn get_locale(process_env: &ProcessEnv, no_exec: bool) -> Option<String> {
+    if cfg!(target_os = "windows") {
+        let output = run_command(
+            no_exec,
+            Command::new("powershell").args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "(Get-Culture).Name",
+            ]),
+        )?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return (!name.is_empty()).then_some(name);
     }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|key| process_env.var(key))
+        .find(|value| !value.is_empty())
 }
 
-/// Format uptime
-fn format_uptime(seconds: u64) -> String {
-    let days = seconds / 86400;
-    let hours = (seconds % 86400) / 3600;
-    let minutes = (seconds % 3600) / 60;
+/// Get the IANA time zone from `TZ`, then `/etc/timezone` (Debian and
+/// derivatives), then the `/etc/localtime` link, then the platform's own
+/// setting
+fn get_timezone(process_env: &ProcessEnv) -> Option<String> {
+    process_env
+        .var("TZ")
+        .and_then(|tz| parse_tz(&tz))
+        .or_else(|| {
+            fs::read_to_string("/etc/timezone")
+                .ok()
+                .and_then(|content| parse_timezone_file(&content))
+        })
+        .or_else(|| {
+            fs::read_link("/etc/localtime")
+                .ok()
+                .and_then(|target| zone_from_path(&target))
+        })
+        .or_else(|| iana_time_zone::get_timezone().ok())
+}
 
-    if days > 0 {
-        format!("{}d {}h {}m", days, hours, minutes)
-    } else if hours > 0 {
-        format!("{}h {}m", hours, minutes)
+/// Zone name from a `TZ` value; a leading ':' marks an
+/// implementation-defined value, usually a path into the zoneinfo database
+fn parse_tz(tz: &str) -> Option<String> {
+    let tz = tz.trim_start_matches(':');
+    if tz.starts_with('/') {
+        zone_from_path(Path::new(tz))
     } else {
-        format!("{}m", minutes)
+        (!tz.is_empty()).then(|| tz.to_string())
     }
 }
 
-/// Collect CPU information
-fn collect_cpu_info(sys: &System) -> Vec<CpuInfo> {
-    let mut cpu_map = std::collections::HashMap::new();
+/// The zone name `/etc/timezone` holds on a line of its own
+fn parse_timezone_file(content: &str) -> Option<String> {
+    let tz = content.trim();
+    (!tz.is_empty()).then(|| tz.to_string())
+}
 
-    // Group CPUs by model to handle multi-core processors
-    for cpu in sys.cpus() {
-        let model = cpu.brand().to_string();
-        let frequency = cpu.frequency();
+/// Zone name from a path into the zoneinfo database, such as the target of
+/// the `/etc/localtime` link, "/usr/share/zoneinfo/Europe/Berlin"
+fn zone_from_path(path: &Path) -> Option<String> {
+    let (_, zone) = path.to_str()?.rsplit_once("zoneinfo/")?;
+    // Some distributions keep "posix/" and "right/" copies of every zone
+    let zone = ["posix/", "right/"]
+        .into_iter()
+        .find_map(|prefix| zone.strip_prefix(prefix))
+        .unwrap_or(zone);
+    (!zone.is_empty()).then(|| zone.to_string())
+}
+
+/// Get the current working directory, shortening the home directory to `~`
+fn get_cwd() -> Option<String> {
+    // Fails when the directory has been removed since launch
+    let cwd = env::current_dir().ok().filter(|cwd| cwd.exists())?;
 
-        let entry = cpu_map.entry(model.clone()).or_insert(CpuInfo {
-            model,
-            cores: 0,
-            frequency,
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    if let Some(rest) = home.and_then(|home| cwd.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        return Some(if rest.as_os_str().is_empty() {
+            "~".to_string()
+        } else {
+            format!("~{}{}", MAIN_SEPARATOR, rest.display())
         });
-        entry.cores += 1;
     }
 
-    cpu_map.into_values().collect()
+    Some(cwd.display().to_string())
 }
 
-/// Get GPU information list
-fn get_gpu_info_list() -> Vec<GpuInfo> {
-    if cfg!(target_os = "windows") {
-        get_gpu_info_windows_list()
-    } else if cfg!(target_os = "linux") {
-        get_gpu_info_linux_list()
-    } else if cfg!(target_os = "macos") {
-        get_gpu_info_macos_list()
-    } else {
-        vec![GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        }]
+/// Read the kernel entropy pool size on Linux
+fn get_entropy_available() -> Option<u32> {
+    if !cfg!(target_os = "linux") {
+        return None;
     }
+    fs::read_to_string("/proc/sys/kernel/random/entropy_avail")
+        .ok()
+        .and_then(|content| parse_entropy(&content))
 }
 
-/// Get GPU information on Windows system (multiple GPUs)
-fn get_gpu_info_windows_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
-
-    match Command::new("wmic")
-        .args(&[
-            "path",
-            "win32_VideoController",
-            "get",
-            "name,AdapterCompatibility",
-            "/format:value",
-        ])
-        .output()
-    {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let mut current_gpu = GpuInfo {
-                name: String::new(),
-                vendor: String::new(),
-            };
+/// Parse the contents of `entropy_avail`
+fn parse_entropy(content: &str) -> Option<u32> {
+    content.trim().parse().ok()
+}
 
-            for line in output_str.lines() {
-                let line = line.trim();
-                if line.starts_with("AdapterCompatibility=")
-                    && !line.trim_end_matches("AdapterCompatibility=").is_empty()
-                {
-                    current_gpu.vendor = line
-                        .trim_start_matches("AdapterCompatibility=")
-                        .trim()
-                        .to_string();
-                } else if line.starts_with("Name=") && !line.trim_end_matches("Name=").is_empty() {
-                    current_gpu.name = line.trim_start_matches("Name=").trim().to_string();
-
-                    // If we have both name and vendor, add to list
-                    if !current_gpu.name.is_empty() {
-                        gpus.push(current_gpu.clone());
-                        current_gpu = GpuInfo {
-                            name: String::new(),
-                            vendor: String::new(),
-                        };
-                    }
-                }
-            }
-        }
-        Err(_) => {}
+/// Read the system-wide open file count and the soft `ulimit -n` on Linux
+fn get_fd_usage() -> (Option<usize>, Option<u64>) {
+    if !cfg!(target_os = "linux") {
+        return (None, None);
     }
+    let open = fs::read_to_string("/proc/sys/fs/file-nr")
+        .ok()
+        .and_then(|content| parse_file_nr(&content));
+    let limit = fs::read_to_string("/proc/self/limits")
+        .ok()
+        .and_then(|content| parse_open_files_limit(&content));
+    (open, limit)
+}
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
+/// Parse `file-nr`: allocated, unused (always 0 on modern kernels) and maximum
+fn parse_file_nr(content: &str) -> Option<usize> {
+    content.split_whitespace().next()?.parse().ok()
+}
+
+/// Parse the soft limit from the "Max open files" row of `/proc/<pid>/limits`
+fn parse_open_files_limit(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|soft| soft.parse().ok())
+}
+
+/// Hottest CPU sensor from hwmon (Linux) or SMC (macOS), preferring the
+/// package-level sensors over per-core ones
+fn get_cpu_temp() -> Option<f32> {
+    let components = Components::new_with_refreshed_list();
+    let cpu_sensors: Vec<_> = components
+        .list()
+        .iter()
+        .filter(|component| {
+            let label = component.label().to_lowercase();
+            ["cpu", "package", "tctl"]
+                .iter()
+                .any(|name| label.contains(name))
+        })
+        .collect();
+
+    let is_package = |label: &str| {
+        let label = label.to_lowercase();
+        label.contains("package") || label.contains("tctl")
+    };
+    let hottest = |package_only: bool| {
+        cpu_sensors
+            .iter()
+            .filter(|component| !package_only || is_package(component.label()))
+            .map(|component| component.temperature())
+            .filter(|temp| temp.is_finite() && *temp > 0.0)
+            .reduce(f32::max)
+    };
+    hottest(true).or_else(|| hottest(false))
+}
+
+/// Read the boost state from cpufreq (AMD, acpi-cpufreq) or intel_pstate
+fn get_turbo_enabled() -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
     }
+    let read_flag = |path: &str| match fs::read_to_string(path).ok()?.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    };
+    read_flag("/sys/devices/system/cpu/cpufreq/boost")
+        .or_else(|| read_flag("/sys/devices/system/cpu/intel_pstate/no_turbo").map(|off| !off))
+}
 
-    gpus
+/// Tell whether the CPU is thermally throttled, from the thermal zone trip
+/// points or, lacking those, from the cpufreq throttle counters (Linux only)
+fn get_thermal_throttling() -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    trip_point_throttling().or_else(throttle_counters_rising)
 }
 
-/// Get GPU information on Linux system (multiple GPUs)
-fn get_gpu_info_linux_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
-
-    match Command::new("lspci").args(&["-mm"]).output() {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if line.contains("VGA compatible controller") || line.contains("3D controller") {
-                    let parts: Vec<&str> = line.split('"').collect();
-                    if parts.len() >= 6 {
-                        gpus.push(GpuInfo {
-                            name: format!("{} {}", parts[3], parts[5]),
-                            vendor: parts[3].to_string(),
-                        });
-                    }
-                }
+/// Whether any thermal zone is at or above its passive trip point, the
+/// temperature at which the kernel starts throttling to cool down
+fn trip_point_throttling() -> Option<bool> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let mut found = false;
+    let mut throttling = false;
+    for zone in fs::read_dir("/sys/class/thermal")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+    {
+        let zone = zone.path();
+        let Some(temp) = read_u64(&zone.join("temp")) else {
+            continue;
+        };
+        for trip in 0.. {
+            let Ok(kind) = fs::read_to_string(zone.join(format!("trip_point_{trip}_type"))) else {
+                break;
+            };
+            if kind.trim() != "passive" {
+                continue;
             }
+            let Some(limit) = read_u64(&zone.join(format!("trip_point_{trip}_temp"))) else {
+                continue;
+            };
+            found = true;
+            throttling |= temp >= limit;
         }
-        Err(_) => {}
     }
+    found.then_some(throttling)
+}
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
+/// Whether the per-CPU `thermal_throttle` event counters (Intel) move over a
+/// short interval
+fn throttle_counters_rising() -> Option<bool> {
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    let read_counters = || -> u64 {
+        fs::read_dir("/sys/devices/system/cpu")
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join("thermal_throttle"))
+            .flat_map(|dir| {
+                ["core_throttle_count", "package_throttle_count"]
+                    .map(|name| read_u64(&dir.join(name)))
+            })
+            .flatten()
+            .sum()
+    };
+
+    // Only some drivers expose the counters
+    read_u64(Path::new(
+        "/sys/devices/system/cpu/cpu0/thermal_throttle/core_throttle_count",
+    ))?;
+    let before = read_counters();
+    thread::sleep(INTERVAL);
+    Some(read_counters() > before)
+}
+
+/// Read the allowlisted sysctl keys from `/proc/sys`, skipping missing ones
+fn get_sysctls(keys: &[String]) -> Vec<(String, String)> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
     }
+    keys.iter()
+        .filter_map(|key| {
+            let path = sysctl_path(key)?;
+            let value = fs::read_to_string(path).ok()?;
+            Some((
+                key.clone(),
+                value.split_whitespace().collect::<Vec<_>>().join(" "),
+            ))
+        })
+        .collect()
+}
 
-    gpus
+/// Map a dotted sysctl key such as `vm.swappiness` to its `/proc/sys` path,
+/// rejecting anything that could escape that directory
+fn sysctl_path(key: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::from("/proc/sys");
+    for part in key.split('.') {
+        if part.is_empty() || part.contains('/') {
+            return None;
+        }
+        path.push(part);
+    }
+    Some(path)
 }
 
-/// Get GPU information on macOS system (multiple GPUs)
-fn get_gpu_info_macos_list() -> Vec<GpuInfo> {
-    let mut gpus = Vec::new();
+/// Environment of the process whose session is described
+struct ProcessEnv {
+    pid: u32,
+    /// `None` reads this process's own environment
+    vars: Option<HashMap<String, String>>,
+}
 
-    match Command::new("system_profiler")
-        .args(&["SPDisplaysDataType", "-json"])
-        .output()
-    {
-        Ok(output) => {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-
-            // Simple parsing to find all GPU names
-            let mut pos = 0;
-            while let Some(start) = output_str[pos..].find("\"_name\" : \"") {
-                let start = pos + start + 11;
-                if let Some(end) = output_str[start..].find('"') {
-                    let gpu_name = output_str[start..start + end].to_string();
-                    gpus.push(GpuInfo {
-                        name: gpu_name.clone(),
-                        vendor: if gpu_name.to_lowercase().contains("nvidia") {
-                            "NVIDIA".to_string()
-                        } else if gpu_name.to_lowercase().contains("amd")
-                            || gpu_name.to_lowercase().contains("radeon")
-                        {
-                            "AMD".to_string()
-                        } else if gpu_name.to_lowercase().contains("intel") {
-                            "Intel".to_string()
-                        } else {
-                            "Unknown".to_string()
-                        },
-                    });
-                    pos = start + end;
-                } else {
-                    break;
-                }
-            }
+impl ProcessEnv {
+    fn current() -> Self {
+        Self {
+            pid: std::process::id(),
+            vars: None,
         }
-        Err(_) => {}
     }
 
-    if gpus.is_empty() {
-        gpus.push(GpuInfo {
-            name: "Unknown GPU".to_string(),
-            vendor: "Unknown".to_string(),
-        });
+    /// Read another process's environment from `/proc/<pid>/environ` (Linux only)
+    fn of_pid(pid: u32) -> AppResult<Self> {
+        if !cfg!(target_os = "linux") {
+            return Err("--pid is only supported on Linux".into());
+        }
+        let blob = fs::read(format!("/proc/{pid}/environ"))
+            .map_err(|err| format!("cannot read the environment of PID {pid}: {err}"))?;
+        Ok(Self {
+            pid,
+            vars: Some(parse_environ(&blob)),
+        })
     }
 
-    gpus
+    fn var(&self, key: &str) -> Option<String> {
+        match &self.vars {
+            Some(vars) => vars.get(key).cloned(),
+            None => env::var(key).ok(),
+        }
+    }
 }
 
-/// Get local IP address
-fn get_local_ip() -> String {
-    match local_ip() {
-        Ok(ip) => ip.to_string(),
-        Err(_) => "Unknown IP".to_string(),
-    }
+/// Parse a NUL-separated `KEY=value` blob as found in `/proc/<pid>/environ`
+fn parse_environ(blob: &[u8]) -> HashMap<String, String> {
+    blob.split(|&byte| byte == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (key, value) = entry.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
 }
 
 /// Get shell information
-fn get_shell_info() -> String {
+fn get_shell_info(process_env: &ProcessEnv, no_exec: bool) -> String {
     // Try to get shell from environment variables
-    if let Ok(shell) = env::var("SHELL") {
+    if let Some(shell) = process_env.var("SHELL") {
         // Extract shell name from path
-        if let Some(shell_name) = shell.split('/').last() {
+        if let Some(shell_name) = shell.split('/').next_back() {
             return shell_name.to_string();
         }
         return shell;
@@ -306,13 +3287,13 @@ fn get_shell_info() -> String {
     // Windows specific shell detection
     if cfg!(target_os = "windows") {
         // Check for PowerShell
-        if env::var("PSModulePath").is_ok() {
+        if process_env.var("PSModulePath").is_some() {
             return "PowerShell".to_string();
         }
 
         // Check for Command Prompt
-        if let Ok(comspec) = env::var("COMSPEC") {
-            if let Some(shell_name) = comspec.split('\\').last() {
+        if let Some(comspec) = process_env.var("COMSPEC") {
+            if let Some(shell_name) = comspec.split('\\').next_back() {
                 return shell_name.replace(".exe", "");
             }
         }
@@ -321,21 +3302,67 @@ fn get_shell_info() -> String {
     }
 
     // Unix-like systems fallback
-    if let Ok(output) = Command::new("ps")
-        .args(&["-p", &std::process::id().to_string(), "-o", "comm="])
-        .output()
-    {
+    if let Some(output) = run_command(
+        no_exec,
+        Command::new("ps").args(["-p", &process_env.pid.to_string(), "-o", "comm="]),
+    ) {
         let shell = String::from_utf8_lossy(&output.stdout).trim().to_string();
         if !shell.is_empty() {
             return shell;
         }
     }
 
-    "Unknown Shell".to_string()
+    UNKNOWN_SHELL.to_string()
+}
+
+/// Get the shell version from the variable the shell sets when it is
+/// exported, otherwise from `<shell> --version` (Unix only)
+fn get_shell_version(process_env: &ProcessEnv, shell: &str, no_exec: bool) -> Option<String> {
+    let variable = match shell {
+        "bash" => "BASH_VERSION",
+        "zsh" => "ZSH_VERSION",
+        "fish" => "FISH_VERSION",
+        "nu" => "NU_VERSION",
+        _ => "",
+    };
+    if let Some(version) = process_env
+        .var(variable)
+        .and_then(|value| parse_version(&value))
+    {
+        return Some(version);
+    }
+
+    if no_exec || cfg!(target_os = "windows") || shell == UNKNOWN_SHELL {
+        return None;
+    }
+    // Run the same binary as $SHELL when that is the shell found
+    let program = process_env
+        .var("SHELL")
+        .filter(|path| path.rsplit('/').next() == Some(shell))
+        .unwrap_or_else(|| shell.to_string());
+    let mut command = Command::new(program);
+    let output = run_command_with_timeout(command.arg("--version"), SHELL_VERSION_TIMEOUT)?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .split_whitespace()
+        .find_map(parse_version)
+}
+
+/// Leading dotted version number of a word, e.g. "5.2.15" from
+/// "5.2.15(1)-release"
+fn parse_version(word: &str) -> Option<String> {
+    let version: String = word
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    let version = version.trim_end_matches('.');
+    (version.contains('.') && version.starts_with(|c: char| c.is_ascii_digit()))
+        .then(|| version.to_string())
 }
 
 /// Get terminal information
-fn get_terminal_info() -> String {
+fn get_terminal_info(process_env: &ProcessEnv, no_exec: bool) -> String {
     // Check common terminal environment variables
     let terminal_vars = [
         "TERM_PROGRAM",          // macOS Terminal, iTerm2, etc.
@@ -349,7 +3376,7 @@ fn get_terminal_info() -> String {
     ];
 
     for var in &terminal_vars {
-        if let Ok(value) = env::var(var) {
+        if let Some(value) = process_env.var(var) {
             match *var {
                 "TERM_PROGRAM" => return value,
                 "TERMINAL_EMULATOR" => return value,
@@ -367,89 +3394,60 @@ fn get_terminal_info() -> String {
     // Windows specific terminal detection
     if cfg!(target_os = "windows") {
         // Check for Windows Terminal
-        if env::var("WT_SESSION").is_ok() {
+        if process_env.var("WT_SESSION").is_some() {
             return "Windows Terminal".to_string();
         }
 
         // Check for ConEmu
-        if env::var("ConEmuPID").is_ok() {
+        if process_env.var("ConEmuPID").is_some() {
             return "ConEmu".to_string();
         }
 
         // Check for Cmder
-        if env::var("CMDER_ROOT").is_ok() {
+        if process_env.var("CMDER_ROOT").is_some() {
             return "Cmder".to_string();
         }
 
         // Try to detect through parent process on Windows
-        if let Ok(output) = Command::new("wmic")
-            .args(&[
-                "process",
-                "where",
-                &format!("ProcessId={}", std::process::id()),
-                "get",
-                "ParentProcessId",
-                "/format:value",
-            ])
-            .output()
-        {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            for line in output_str.lines() {
-                if let Some(ppid_str) = line.strip_prefix("ParentProcessId=") {
-                    if let Ok(ppid) = ppid_str.trim().parse::<u32>() {
-                        if let Ok(parent_output) = Command::new("wmic")
-                            .args(&[
-                                "process",
-                                "where",
-                                &format!("ProcessId={}", ppid),
-                                "get",
-                                "Name",
-                                "/format:value",
-                            ])
-                            .output()
-                        {
-                            let parent_str = String::from_utf8_lossy(&parent_output.stdout);
-                            for parent_line in parent_str.lines() {
-                                if let Some(name) = parent_line.strip_prefix("Name=") {
-                                    let name = name.trim();
-                                    if !name.is_empty() {
-                                        return match name {
-                                            "WindowsTerminal.exe" => "Windows Terminal".to_string(),
-                                            "ConEmu64.exe" | "ConEmu.exe" => "ConEmu".to_string(),
-                                            "cmd.exe" => "Command Prompt".to_string(),
-                                            "powershell.exe" => "PowerShell".to_string(),
-                                            "pwsh.exe" => "PowerShell Core".to_string(),
-                                            _ => name.replace(".exe", ""),
-                                        };
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        let process = |pid: u32, property: &str| {
+            cim_instances(
+                no_exec,
+                "Win32_Process",
+                Some(&format!("ProcessId={pid}")),
+                &[property],
+            )
+            .into_iter()
+            .next()
+        };
+        let parent_name = process(process_env.pid, "ParentProcessId")
+            .and_then(|instance| cim_u64(&instance, "ParentProcessId"))
+            .and_then(|ppid| process(ppid as u32, "Name"))
+            .and_then(|instance| cim_string(&instance, "Name"));
+        if let Some(name) = parent_name {
+            return match name.as_str() {
+                "WindowsTerminal.exe" => "Windows Terminal".to_string(),
+                "ConEmu64.exe" | "ConEmu.exe" => "ConEmu".to_string(),
+                "cmd.exe" => "Command Prompt".to_string(),
+                "powershell.exe" => "PowerShell".to_string(),
+                "pwsh.exe" => "PowerShell Core".to_string(),
+                _ => name.replace(".exe", ""),
+            };
         }
 
         return "Command Prompt".to_string();
     }
 
-    // Unix-like systems: try to get terminal from TERM or parent process
-    if let Ok(term) = env::var("TERM") {
+    // Unix-like systems: look for the emulator among our ancestors, which
+    // sees through login shells, sudo and the like
+    if let Some(terminal) = terminal_from_ancestry(process_env.pid, no_exec) {
+        return terminal;
+    }
+
+    // Then guess from TERM
+    if let Some(term) = process_env.var("TERM") {
         // Common terminal identifiers
         match term.as_str() {
-            "xterm-256color" | "xterm" => {
-                // Try to get more specific terminal info
-                if let Ok(output) = Command::new("ps")
-                    .args(&["-o", "comm=", "-p", &std::process::id().to_string()])
-                    .output()
-                {
-                    let parent = String::from_utf8_lossy(&output.stdout).trim().to_string();
-                    if !parent.is_empty() && parent != "sh" && parent != "bash" {
-                        return parent;
-                    }
-                }
-                return "xterm".to_string();
-            }
+            "xterm-256color" | "xterm" => return "xterm".to_string(),
             "screen" => return "GNU Screen".to_string(),
             "tmux" => return "tmux".to_string(),
             _ => {
@@ -462,5 +3460,791 @@ fn get_terminal_info() -> String {
         }
     }
 
-    "Unknown Terminal".to_string()
+    UNKNOWN_TERMINAL.to_string()
+}
+
+/// Terminal emulators recognized among the ancestors of a process, by
+/// process name; Linux truncates names to 15 bytes
+const TERMINAL_PROCESSES: &[(&str, &str)] = &[
+    ("alacritty", "Alacritty"),
+    ("kitty", "Kitty"),
+    ("wezterm-gui", "WezTerm"),
+    ("ghostty", "Ghostty"),
+    ("foot", "foot"),
+    ("footclient", "foot"),
+    ("konsole", "Konsole"),
+    ("gnome-terminal-", "GNOME Terminal"),
+    ("gnome-terminal-server", "GNOME Terminal"),
+    ("kgx", "GNOME Console"),
+    ("xfce4-terminal", "Xfce Terminal"),
+    ("mate-terminal", "MATE Terminal"),
+    ("lxterminal", "LXTerminal"),
+    ("qterminal", "QTerminal"),
+    ("tilix", "Tilix"),
+    ("terminator", "Terminator"),
+    ("terminology", "Terminology"),
+    ("guake", "Guake"),
+    ("yakuake", "Yakuake"),
+    ("sakura", "Sakura"),
+    ("xterm", "xterm"),
+    ("urxvt", "urxvt"),
+    ("urxvtd", "urxvt"),
+    ("st", "st"),
+    ("Terminal", "Apple Terminal"),
+    ("iTerm2", "iTerm2"),
+    ("Hyper", "Hyper"),
+    ("tabby", "Tabby"),
+];
+
+/// Ancestors checked before giving up, in case the chain loops
+const MAX_ANCESTRY_DEPTH: usize = 32;
+
+/// Walk up the parents of `pid` to the first known terminal emulator,
+/// stopping at PID 1
+fn terminal_from_ancestry(pid: u32, no_exec: bool) -> Option<String> {
+    let mut pid = process_parent_and_name(pid, no_exec)?.0;
+    for _ in 0..MAX_ANCESTRY_DEPTH {
+        if pid <= 1 {
+            break;
+        }
+        let (parent, name) = process_parent_and_name(pid, no_exec)?;
+        if let Some((_, terminal)) = TERMINAL_PROCESSES
+            .iter()
+            .find(|(process, _)| *process == name)
+        {
+            return Some(terminal.to_string());
+        }
+        pid = parent;
+    }
+    None
+}
+
+/// Parent PID and name of a process, from `/proc/<pid>/stat` on Linux and
+/// `ps` elsewhere
+fn process_parent_and_name(pid: u32, no_exec: bool) -> Option<(u32, String)> {
+    if cfg!(target_os = "linux") {
+        // "pid (name) state ppid ...", where the name may contain anything
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let (head, rest) = stat.rsplit_once(')')?;
+        let name = head.split_once('(')?.1;
+        let parent = rest.split_whitespace().nth(1)?.parse().ok()?;
+        Some((parent, name.to_string()))
+    } else {
+        let output = run_command(
+            no_exec,
+            Command::new("ps").args(["-o", "ppid=,comm=", "-p", &pid.to_string()]),
+        )?;
+        let output = String::from_utf8_lossy(&output.stdout);
+        let (parent, command) = output.trim().split_once(char::is_whitespace)?;
+        // macOS prints the full executable path
+        let name = command.trim().rsplit('/').next()?;
+        Some((parent.parse().ok()?, name.to_string()))
+    }
+}
+
+/// Best-effort check for an installed Nerd Font
+fn detect_nerd_font(no_exec: bool) -> bool {
+    if cfg!(target_os = "linux") {
+        run_command(no_exec, Command::new("fc-list").args([":", "family"])).is_some_and(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(is_nerd_font_name)
+        })
+    } else if cfg!(target_os = "windows") {
+        let mut dirs = Vec::new();
+        if let Ok(windir) = env::var("WINDIR") {
+            dirs.push(PathBuf::from(windir).join("Fonts"));
+        }
+        if let Ok(local) = env::var("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(local).join("Microsoft\\Windows\\Fonts"));
+        }
+        dirs.iter().any(|dir| dir_has_nerd_font(dir))
+    } else if cfg!(target_os = "macos") {
+        let mut dirs = vec![PathBuf::from("/Library/Fonts")];
+        if let Ok(home) = env::var("HOME") {
+            dirs.push(PathBuf::from(home).join("Library/Fonts"));
+        }
+        dirs.iter().any(|dir| dir_has_nerd_font(dir))
+    } else {
+        false
+    }
+}
+
+/// Whether a font family or file name belongs to a Nerd Font
+fn is_nerd_font_name(name: &str) -> bool {
+    name.contains("Nerd Font") || name.contains("NerdFont") || name.ends_with(" NF")
+}
+
+/// Scan a font directory for Nerd Font files
+fn dir_has_nerd_font(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| is_nerd_font_name(&entry.file_name().to_string_lossy()))
+    })
+}
+
+/// Detect terminal size and color capabilities
+fn get_terminal_caps() -> TerminalCaps {
+    let term = env::var("TERM").ok();
+    let colorterm = env::var("COLORTERM").ok();
+    let truecolor = colorterm
+        .as_deref()
+        .is_some_and(|value| value == "truecolor" || value == "24bit");
+    let (columns, rows) = match crossterm::terminal::size() {
+        Ok((columns, rows)) => (Some(columns), Some(rows)),
+        Err(_) => (None, None),
+    };
+
+    TerminalCaps {
+        truecolor,
+        columns,
+        rows,
+        term,
+        colorterm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_collection_leaves_the_slow_fields_empty() {
+        let config = Config {
+            fast: true,
+            ..Config::default()
+        };
+        let info = SystemInfo::collect(&config).unwrap();
+
+        assert!(info.gpus.is_empty());
+        assert!(info.packages.is_empty());
+        assert!(info.cpus.is_empty());
+        assert_eq!(info.process_count, None);
+        assert_eq!(info.running_process_count, None);
+        assert_eq!(info.local_ip, UNKNOWN_IP);
+        assert_eq!(info.shell, UNKNOWN_SHELL);
+        assert_eq!(info.terminal, UNKNOWN_TERMINAL);
+        assert_ne!(info.os_name, "");
+        assert!(info.memory_total > 0);
+    }
+
+    #[test]
+    fn format_uptime_rounds_down_to_minutes() {
+        assert_eq!(format_uptime(0, false), "0m");
+        assert_eq!(format_uptime(60, false), "1m");
+        assert_eq!(format_uptime(3599, false), "59m");
+        assert_eq!(format_uptime(3600, false), "1h 0m");
+        assert_eq!(format_uptime(86400, false), "1d 0h 0m");
+        assert_eq!(
+            format_uptime(3 * 86400 + 5 * 3600 + 7 * 60 + 9, false),
+            "3d 5h 7m"
+        );
+    }
+
+    #[test]
+    fn format_uptime_shows_seconds_under_an_hour() {
+        assert_eq!(format_uptime(0, true), "0s");
+        assert_eq!(format_uptime(45, true), "45s");
+        assert_eq!(format_uptime(60, true), "1m 0s");
+        assert_eq!(format_uptime(3599, true), "59m 59s");
+        // Seconds are noise once the hours show
+        assert_eq!(format_uptime(3600, true), "1h 0m");
+        assert_eq!(format_uptime(86400, true), "1d 0h 0m");
+        assert_eq!(
+            format_uptime(3 * 86400 + 5 * 3600 + 7 * 60 + 9, true),
+            "3d 5h 7m"
+        );
+    }
+
+    #[test]
+    fn parse_mm_stat_reads_the_first_two_counters() {
+        assert_eq!(
+            parse_mm_stat(
+                "1073741824  268435456  281018368        0  289406976      123        0     1024      512\n"
+            ),
+            Some((1_073_741_824, 268_435_456))
+        );
+    }
+
+    #[test]
+    fn parse_mm_stat_rejects_empty_and_garbled_devices() {
+        assert_eq!(parse_mm_stat("0 0 0 0 0 0 0 0 0\n"), None);
+        assert_eq!(parse_mm_stat("4096 0 0 0 0 0 0 0 0\n"), None);
+        assert_eq!(parse_mm_stat("4096"), None);
+        assert_eq!(parse_mm_stat(""), None);
+        assert_eq!(parse_mm_stat("abc def"), None);
+        assert_eq!(parse_mm_stat("4096 -1"), None);
+    }
+
+    #[test]
+    fn parse_cpu_list_counts_values_and_ranges() {
+        assert_eq!(parse_cpu_list("0"), Some(1));
+        assert_eq!(parse_cpu_list("5"), Some(1));
+        assert_eq!(parse_cpu_list("0-7"), Some(8));
+        assert_eq!(parse_cpu_list("0-13,15"), Some(15));
+        assert_eq!(parse_cpu_list("0,2,4-5"), Some(4));
+        assert_eq!(parse_cpu_list("0-3,8-11\n"), Some(8));
+    }
+
+    #[test]
+    fn parse_cpu_list_rejects_malformed_lists() {
+        assert_eq!(parse_cpu_list(""), None);
+        assert_eq!(parse_cpu_list("\n"), None);
+        assert_eq!(parse_cpu_list("a-b"), None);
+        assert_eq!(parse_cpu_list("7-3"), None);
+        assert_eq!(parse_cpu_list("0-3,x"), None);
+        assert_eq!(parse_cpu_list("0-"), None);
+    }
+
+    #[test]
+    fn parse_file_nr_reads_the_allocated_count() {
+        assert_eq!(
+            parse_file_nr("12480\t0\t9223372036854775807\n"),
+            Some(12480)
+        );
+        assert_eq!(parse_file_nr("12480"), Some(12480));
+        assert_eq!(parse_file_nr(""), None);
+        assert_eq!(parse_file_nr("\n"), None);
+        assert_eq!(parse_file_nr("n/a 0 0"), None);
+    }
+
+    #[test]
+    fn parse_open_files_limit_reads_the_soft_limit() {
+        let limits = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max processes             63465                63465                processes
+Max open files            1024                 524288               files
+Max locked memory         8388608              8388608              bytes
+";
+        assert_eq!(parse_open_files_limit(limits), Some(1024));
+        assert_eq!(parse_open_files_limit("Max open files"), None);
+        assert_eq!(parse_open_files_limit(""), None);
+    }
+
+    #[test]
+    fn parse_entropy_reads_the_pool_size() {
+        assert_eq!(parse_entropy("256"), Some(256));
+        assert_eq!(parse_entropy("3754\n"), Some(3754));
+        assert_eq!(parse_entropy("  256 \t\n"), Some(256));
+        assert_eq!(parse_entropy("unknown"), None);
+        assert_eq!(parse_entropy("-1"), None);
+        assert_eq!(parse_entropy(""), None);
+    }
+
+    #[test]
+    fn parse_environ_splits_variables() {
+        let env = parse_environ(b"SHELL=/bin/zsh\0TERM=xterm-kitty\0EMPTY=\0OPTS=a=b\0");
+        assert_eq!(env.len(), 4);
+        assert_eq!(env["SHELL"], "/bin/zsh");
+        assert_eq!(env["TERM"], "xterm-kitty");
+        assert_eq!(env["EMPTY"], "");
+        // Only the first `=` separates the name
+        assert_eq!(env["OPTS"], "a=b");
+    }
+
+    #[test]
+    fn parse_environ_skips_entries_without_a_value() {
+        let env = parse_environ(b"NOEQUALS\0\0USER=ferris");
+        assert_eq!(env.len(), 1);
+        assert_eq!(env["USER"], "ferris");
+        assert!(parse_environ(b"").is_empty());
+    }
+
+    fn interfaces(list: &[(&str, &str)]) -> Vec<(String, IpAddr)> {
+        list.iter()
+            .map(|&(name, ip)| (name.to_string(), ip.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn detect_vpn_interface_matches_tunnels() {
+        let vpn = |name: &str| detect_vpn_interface(&interfaces(&[(name, "10.8.0.2")]));
+        assert_eq!(vpn("wg0").as_deref(), Some("wg0"));
+        assert_eq!(vpn("tun1").as_deref(), Some("tun1"));
+        assert_eq!(vpn("tap0").as_deref(), Some("tap0"));
+        assert_eq!(vpn("utun3").as_deref(), Some("utun3"));
+        assert_eq!(vpn("wg").as_deref(), Some("wg"));
+    }
+
+    #[test]
+    fn detect_vpn_interface_ignores_other_interfaces() {
+        let list = interfaces(&[
+            ("lo", "127.0.0.1"),
+            ("wlan0", "192.168.1.20"),
+            ("docker0", "172.17.0.1"),
+            ("wgx", "10.8.0.2"),
+            // An idle macOS tunnel with only a link-local address
+            ("utun0", "fe80::1"),
+        ]);
+        assert_eq!(detect_vpn_interface(&list), None);
+    }
+
+    #[test]
+    fn interface_ip_picks_the_requested_family() {
+        let list = interfaces(&[
+            ("eth0", "fd00::5"),
+            ("eth0", "192.168.1.20"),
+            ("wlan0", "fe80::2"),
+        ]);
+        let ip = |name, version| interface_ip(&list, name, version).map(|ip| ip.to_string());
+        assert_eq!(ip("eth0", IpVersion::V4).as_deref(), Some("192.168.1.20"));
+        assert_eq!(ip("eth0", IpVersion::V6).as_deref(), Some("fd00::5"));
+        assert_eq!(ip("eth0", IpVersion::Auto).as_deref(), Some("192.168.1.20"));
+        // Auto falls back to IPv6 on an IPv6-only interface
+        assert_eq!(ip("wlan0", IpVersion::Auto).as_deref(), Some("fe80::2"));
+        assert_eq!(ip("wlan0", IpVersion::V4), None);
+        assert_eq!(ip("eth9", IpVersion::Auto), None);
+    }
+
+    #[test]
+    fn parse_proc_route_finds_the_default_gateway() {
+        let route = "\
+Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t0000A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\t0\t0\t0
+wlan0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+";
+        assert_eq!(parse_proc_route(route).as_deref(), Some("192.168.1.1"));
+    }
+
+    #[test]
+    fn parse_proc_route_needs_a_default_route_with_a_gateway() {
+        let header = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n";
+        assert_eq!(parse_proc_route(header), None);
+        let link_only = format!("{header}eth0\t00000000\t00000000\t0001\t0\t0\t0\t00000000\n");
+        assert_eq!(parse_proc_route(&link_only), None);
+        let garbled = format!("{header}eth0\t00000000\tzzzz\n");
+        assert_eq!(parse_proc_route(&garbled), None);
+    }
+
+    fn modes(displays: &[DisplayInfo]) -> Vec<(u32, u32, Option<f32>)> {
+        displays
+            .iter()
+            .map(|display| (display.width, display.height, display.refresh_hz))
+            .collect()
+    }
+
+    #[test]
+    fn parse_xrandr_puts_the_primary_output_first() {
+        let output = "\
+Screen 0: minimum 320 x 200, current 4480 x 1440, maximum 16384 x 16384
+eDP-1 connected 1920x1080+2560+0 (normal left inverted right x axis y axis) 344mm x 194mm
+   1920x1080     60.02*+  59.93
+   1680x1050     59.88
+DP-1 disconnected (normal left inverted right x axis y axis)
+HDMI-1 connected (normal left inverted right x axis y axis)
+   1920x1080     60.00 +
+DP-2 connected primary 2560x1440+0+0 (normal left inverted right x axis y axis) 597mm x 336mm
+   2560x1440    143.91*+  59.95
+   1920x1080     60.00
+";
+        // HDMI-1 is connected but switched off, so it has no current mode
+        assert_eq!(
+            modes(&parse_xrandr(output)),
+            [(2560, 1440, Some(143.91)), (1920, 1080, Some(60.02))]
+        );
+
+        // A primary output without an active mode doesn't promote the next one
+        let output = "\
+HDMI-1 connected primary (normal left inverted right x axis y axis)
+   1920x1080     60.00 +
+eDP-1 connected 1920x1200+0+0 (normal left inverted right x axis y axis) 302mm x 188mm
+   1920x1200     59.95 +
+   1280x800      60.00*
+DP-1 connected 1280x1024+1280+0 (normal left inverted right x axis y axis) 338mm x 270mm
+   1280x1024     75.02*+
+";
+        assert_eq!(
+            modes(&parse_xrandr(output)),
+            [(1280, 800, Some(60.0)), (1280, 1024, Some(75.02))]
+        );
+        assert!(parse_xrandr("").is_empty());
+    }
+
+    #[test]
+    fn parse_wlr_randr_reads_the_current_mode_of_enabled_outputs() {
+        let output = "\
+DP-1 \"Dell Inc. DELL U2720Q 8KL1N13 (DP-1)\"
+  Make: Dell Inc.
+  Model: DELL U2720Q
+  Enabled: yes
+  Modes:
+    1920x1080 px, 60.000000 Hz
+    3840x2160 px, 60.000000 Hz (preferred, current)
+  Position: 0,0
+  Scale: 1.500000
+HDMI-A-1 \"LG Electronics LG HDR 4K (HDMI-A-1)\"
+  Enabled: no
+  Modes:
+    3840x2160 px, 60.000000 Hz (preferred, current)
+eDP-1 \"BOE 0x0BCA (eDP-1)\"
+  Enabled: yes
+  Modes:
+    2256x1504 px, 59.999001 Hz (preferred)
+    1920x1280 px, 48.000000 Hz (current)
+";
+        assert_eq!(
+            modes(&parse_wlr_randr(output)),
+            [(3840, 2160, Some(60.0)), (1920, 1280, Some(48.0))]
+        );
+        assert!(parse_wlr_randr("").is_empty());
+    }
+
+    #[test]
+    fn parse_macos_displays_puts_the_main_display_first() {
+        assert_eq!(
+            modes(&parse_macos_displays(SP_DISPLAYS)),
+            // The built-in display reports no refresh rate
+            [(3072, 1920, None), (3840, 2160, Some(60.0))]
+        );
+        assert!(parse_macos_displays("{}").is_empty());
+    }
+
+    #[test]
+    fn chassis_from_dmi_code_groups_the_smbios_types() {
+        let cases = [
+            (1, None),
+            (2, None),
+            (3, Some("Desktop")),
+            (4, Some("Desktop")),
+            (5, Some("Desktop")),
+            (6, Some("Desktop")),
+            (7, Some("Desktop")),
+            (8, Some("Laptop")),
+            (9, Some("Laptop")),
+            (10, Some("Laptop")),
+            (11, Some("Laptop")),
+            (12, None),
+            (13, Some("Desktop")),
+            (14, Some("Laptop")),
+            (15, Some("Desktop")),
+            (16, Some("Desktop")),
+            (17, Some("Server")),
+            (23, Some("Server")),
+            (24, Some("Desktop")),
+            (25, Some("Server")),
+            (28, Some("Server")),
+            (29, Some("Server")),
+            (30, Some("Laptop")),
+            (31, Some("Laptop")),
+            (32, Some("Laptop")),
+            (34, Some("Desktop")),
+            (35, Some("Desktop")),
+            (36, Some("Desktop")),
+            (0, None),
+            (99, None),
+        ];
+        for (code, chassis) in cases {
+            assert_eq!(chassis_from_dmi_code(code), chassis, "code {code}");
+        }
+    }
+
+    fn disk(mount_point: &str, total_gib: u64, used_gib: f64) -> DiskInfo {
+        const GIB: u64 = 1 << 30;
+        DiskInfo {
+            mount_point: mount_point.to_string(),
+            fs_type: "ext4".to_string(),
+            total: total_gib * GIB,
+            used: (used_gib * GIB as f64) as u64,
+        }
+    }
+
+    fn sorted(sort: DiskSort) -> Vec<String> {
+        let mut disks = [
+            disk("/home", 1000, 500.0),
+            disk("/", 500, 100.0),
+            disk("/boot", 1, 0.9),
+        ];
+        sort_disks(&mut disks, sort);
+        disks.into_iter().map(|disk| disk.mount_point).collect()
+    }
+
+    #[test]
+    fn sort_disks_by_mount_point() {
+        assert_eq!(sorted(DiskSort::Mount), ["/", "/boot", "/home"]);
+    }
+
+    #[test]
+    fn sort_disks_fullest_first() {
+        assert_eq!(sorted(DiskSort::Usage), ["/boot", "/home", "/"]);
+    }
+
+    #[test]
+    fn sort_disks_largest_first() {
+        assert_eq!(sorted(DiskSort::Size), ["/home", "/", "/boot"]);
+    }
+
+    #[test]
+    fn sort_disks_breaks_ties_by_mount_point() {
+        let mut disks = [disk("/srv", 100, 50.0), disk("/data", 100, 50.0)];
+        sort_disks(&mut disks, DiskSort::Usage);
+        assert_eq!(disks[0].mount_point, "/data");
+        sort_disks(&mut disks, DiskSort::Size);
+        assert_eq!(disks[0].mount_point, "/data");
+    }
+
+    fn process_env(vars: &[(&str, &str)]) -> ProcessEnv {
+        ProcessEnv {
+            pid: 1,
+            vars: Some(
+                vars.iter()
+                    .map(|&(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn locale_comes_from_the_first_variable_set() {
+        if cfg!(target_os = "windows") {
+            return;
+        }
+        let locale = |vars| get_locale(&process_env(vars), true);
+        assert_eq!(
+            locale(&[("LANG", "en_US.UTF-8")]).as_deref(),
+            Some("en_US.UTF-8")
+        );
+        assert_eq!(
+            locale(&[("LANG", "en_US.UTF-8"), ("LC_CTYPE", "de_DE.UTF-8")]).as_deref(),
+            Some("de_DE.UTF-8")
+        );
+        assert_eq!(
+            locale(&[("LANG", "en_US.UTF-8"), ("LC_ALL", "C")]).as_deref(),
+            Some("C")
+        );
+        // An empty LC_ALL doesn't override anything
+        assert_eq!(
+            locale(&[("LANG", "fr_FR.UTF-8"), ("LC_ALL", "")]).as_deref(),
+            Some("fr_FR.UTF-8")
+        );
+        assert_eq!(locale(&[]), None);
+    }
+
+    #[test]
+    fn parse_tz_accepts_names_and_zoneinfo_paths() {
+        assert_eq!(parse_tz("Europe/Berlin").as_deref(), Some("Europe/Berlin"));
+        assert_eq!(
+            parse_tz(":America/New_York").as_deref(),
+            Some("America/New_York")
+        );
+        assert_eq!(
+            parse_tz(":/usr/share/zoneinfo/Asia/Tokyo").as_deref(),
+            Some("Asia/Tokyo")
+        );
+        assert_eq!(parse_tz("/etc/localtime"), None);
+        assert_eq!(parse_tz(""), None);
+        assert_eq!(parse_tz(":"), None);
+    }
+
+    #[test]
+    fn parse_timezone_file_reads_the_zone_name() {
+        assert_eq!(
+            parse_timezone_file("Europe/Berlin\n").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            parse_timezone_file("  Etc/UTC  "),
+            Some("Etc/UTC".to_string())
+        );
+        assert_eq!(parse_timezone_file("\n"), None);
+    }
+
+    #[test]
+    fn zone_from_path_reads_localtime_links() {
+        let zone = |path: &str| zone_from_path(Path::new(path));
+        assert_eq!(
+            zone("/usr/share/zoneinfo/Europe/Berlin").as_deref(),
+            Some("Europe/Berlin")
+        );
+        assert_eq!(
+            zone("../usr/share/zoneinfo/America/Argentina/Buenos_Aires").as_deref(),
+            Some("America/Argentina/Buenos_Aires")
+        );
+        // macOS
+        assert_eq!(
+            zone("/var/db/timezone/zoneinfo/Australia/Sydney").as_deref(),
+            Some("Australia/Sydney")
+        );
+        assert_eq!(
+            zone("/usr/share/zoneinfo/posix/UTC").as_deref(),
+            Some("UTC")
+        );
+        assert_eq!(zone("/usr/share/zoneinfo/"), None);
+        assert_eq!(zone("/etc/localtime"), None);
+    }
+
+    #[test]
+    fn parse_lspci_keeps_display_controllers() {
+        let output = concat!(
+            "00:02.0 \"VGA compatible controller\" \"Intel Corporation\" \"AlderLake-S GT1\" -r0c -p00 \"Dell\" \"Device 0a9f\"\n",
+            "00:14.0 \"USB controller\" \"Intel Corporation\" \"Alder Lake-S PCH USB 3.2 Gen 2x2 XHCI Controller\" -r11 -p30 \"Dell\" \"Device 0a9f\"\n",
+            "01:00.0 \"3D controller\" \"NVIDIA Corporation\" \"GA107M [GeForce RTX 3050 Mobile]\" -ra1 \"Dell\" \"Device 0a9f\"\n",
+        );
+        let gpus = parse_lspci(output);
+
+        let names: Vec<&str> = gpus.iter().map(|gpu| gpu.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Intel Corporation AlderLake-S GT1",
+                "NVIDIA Corporation GA107M [GeForce RTX 3050 Mobile]",
+            ]
+        );
+        assert_eq!(gpus[1].vendor, "NVIDIA Corporation");
+        assert!(parse_lspci("").is_empty());
+    }
+
+    /// `system_profiler SPDisplaysDataType -json`, trimmed: a discrete GPU
+    /// driving two displays and an integrated one without a vendor field
+    const SP_DISPLAYS: &str = r#"{
+      "SPDisplaysDataType" : [
+        {
+          "_name" : "AMD Radeon Pro 5500M",
+          "spdisplays_mtlgpufamilysupport" : "spdisplays_metal3",
+          "spdisplays_ndrvs" : [
+            {
+              "_name" : "DELL U2720Q",
+              "_spdisplays_resolution" : "3840 x 2160 @ 60.00Hz"
+            },
+            {
+              "_name" : "Color LCD",
+              "_spdisplays_resolution" : "3072 x 1920 Retina",
+              "spdisplays_main" : "spdisplays_yes"
+            }
+          ],
+          "spdisplays_vendor" : "sppci_vendor_amd (0x1002)",
+          "spdisplays_vram" : "8 GB",
+          "sppci_model" : "AMD Radeon Pro 5500M"
+        },
+        {
+          "_name" : "Intel UHD Graphics 630",
+          "spdisplays_metal" : "spdisplays_metal_family2",
+          "spdisplays_vram_shared" : "1536 MB",
+          "sppci_model" : "Intel UHD Graphics 630"
+        }
+      ]
+    }"#;
+
+    #[test]
+    fn parse_macos_gpus_skips_attached_displays() {
+        let gpus = parse_macos_gpus(SP_DISPLAYS);
+
+        let names: Vec<&str> = gpus.iter().map(|gpu| gpu.name.as_str()).collect();
+        assert_eq!(names, ["AMD Radeon Pro 5500M", "Intel UHD Graphics 630"]);
+        assert_eq!(gpus[0].vendor, "AMD");
+        assert_eq!(gpus[0].vram_total, Some(8 << 30));
+        assert_eq!(gpus[0].metal.as_deref(), Some("Metal 3"));
+        // No vendor field, so it comes from the model name
+        assert_eq!(gpus[1].vendor, "Intel");
+        assert_eq!(gpus[1].vram_total, Some(1536 << 20));
+        assert_eq!(gpus[1].metal.as_deref(), Some("Metal 2"));
+
+        assert!(parse_macos_gpus("not json").is_empty());
+    }
+
+    #[test]
+    fn parse_macos_vram_reads_megabytes_and_gigabytes() {
+        assert_eq!(parse_macos_vram("1536 MB"), Some(1536 << 20));
+        assert_eq!(parse_macos_vram(" 4 GB "), Some(4 << 30));
+        assert_eq!(parse_macos_vram("4GB"), None);
+        assert_eq!(parse_macos_vram("4 TB"), None);
+    }
+
+    #[test]
+    fn clean_cpu_name_strips_marks_and_trailing_noise() {
+        assert_eq!(
+            clean_cpu_name("Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz"),
+            "Intel Core i7-9750H"
+        );
+        // The whole model survives, not just its first words
+        assert_eq!(
+            clean_cpu_name("AMD Ryzen 7 7800X3D 8-Core Processor"),
+            "AMD Ryzen 7 7800X3D"
+        );
+        assert_eq!(clean_cpu_name("Apple M2 Pro"), "Apple M2 Pro");
+        assert_eq!(clean_cpu_name("  Neoverse-N1  "), "Neoverse-N1");
+    }
+
+    #[test]
+    fn parse_brand_frequency_reads_the_nominal_clock() {
+        assert_eq!(
+            parse_brand_frequency("Intel(R) Core(TM) i7-9750H CPU @ 2.60GHz"),
+            Some(2600)
+        );
+        assert_eq!(
+            parse_brand_frequency("Intel(R) Xeon(R) CPU E5-2680 v4 @ 2.40 GHz"),
+            Some(2400)
+        );
+        // AMD and Apple leave the clock out of the brand string
+        assert_eq!(
+            parse_brand_frequency("AMD Ryzen 7 7800X3D 8-Core Processor"),
+            None
+        );
+        assert_eq!(parse_brand_frequency("Apple M2 Pro"), None);
+    }
+
+    #[test]
+    fn parse_os_release_unquotes_values_and_skips_comments() {
+        let release = parse_os_release(
+            "# Written by the distribution\n\
+             NAME=\"Ubuntu\"\n\
+             VERSION_ID='24.04'\n\
+             ID=ubuntu\n\
+             \n  # indented comment\n\
+             PRETTY_NAME=\"Ubuntu 24.04.1 LTS\"\n\
+             HOME_URL=\"https://www.ubuntu.com/\"\n\
+             TAGLINE=\"Say \\\"hi\\\"\"\n",
+        );
+        assert_eq!(release["NAME"], "Ubuntu");
+        assert_eq!(release["VERSION_ID"], "24.04");
+        assert_eq!(release["ID"], "ubuntu");
+        assert_eq!(release["PRETTY_NAME"], "Ubuntu 24.04.1 LTS");
+        assert_eq!(release["HOME_URL"], "https://www.ubuntu.com/");
+        assert_eq!(release["TAGLINE"], "Say \"hi\"");
+        assert_eq!(release.len(), 6);
+    }
+
+    #[test]
+    fn os_identity_falls_back_without_pretty_name() {
+        let identity = |content: &str| {
+            os_identity_from_release(
+                &parse_os_release(content),
+                "Linux".to_string(),
+                "rolling".to_string(),
+            )
+        };
+
+        assert_eq!(
+            identity(
+                "NAME=\"Ubuntu\"\nID=ubuntu\nPRETTY_NAME=\"Ubuntu 24.04.1 LTS\"\nVERSION_ID=\"24.04\"\n"
+            ),
+            (
+                "Ubuntu".to_string(),
+                "24.04.1 LTS".to_string(),
+                Some("ubuntu".to_string())
+            )
+        );
+        // No PRETTY_NAME, so VERSION_ID
+        assert_eq!(
+            identity("NAME=Fedora Linux\nID=fedora\nVERSION_ID=41\n"),
+            (
+                "Fedora Linux".to_string(),
+                "41".to_string(),
+                Some("fedora".to_string())
+            )
+        );
+        // Neither, so sysinfo's version
+        assert_eq!(
+            identity("NAME=\"Arch Linux\"\nID=arch\n"),
+            (
+                "Arch Linux".to_string(),
+                "rolling".to_string(),
+                Some("arch".to_string())
+            )
+        );
+        assert_eq!(
+            identity(""),
+            ("Linux".to_string(), "rolling".to_string(), None)
+        );
+    }
 }
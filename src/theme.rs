@@ -0,0 +1,249 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+use ratatui::style::Color;
+
+use crate::config::{Config, ThemePreset};
+
+/// Colors of the panel title and border, section labels, list items and
+/// values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub accent: Color,
+    pub border: Color,
+    pub label: Color,
+    pub item: Color,
+    pub value: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            accent: Color::Cyan,
+            border: Color::Reset,
+            label: Color::Cyan,
+            item: Color::Yellow,
+            value: Color::Reset,
+        }
+    }
+}
+
+impl Palette {
+    /// Pick the palette for this run: monochrome when `NO_COLOR` is set,
+    /// derived from the wallpaper when requested and supported, otherwise
+    /// the configured preset
+    pub fn resolve(config: &Config) -> Self {
+        if no_color() {
+            return Self::preset(ThemePreset::Monochrome);
+        }
+
+        #[cfg(feature = "wallpaper")]
+        if config.theme_from_wallpaper {
+            if let Some(palette) = wallpaper::palette(config.no_exec || config.fast) {
+                return palette;
+            }
+        }
+
+        Self::preset(config.theme)
+    }
+
+    pub fn preset(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Default => Self::default(),
+            ThemePreset::Monochrome => Self {
+                accent: Color::Reset,
+                border: Color::Reset,
+                label: Color::Reset,
+                item: Color::Reset,
+                value: Color::Reset,
+            },
+            ThemePreset::Dracula => Self {
+                accent: Color::Rgb(189, 147, 249),
+                border: Color::Rgb(98, 114, 164),
+                label: Color::Rgb(139, 233, 253),
+                item: Color::Rgb(255, 121, 198),
+                value: Color::Rgb(248, 248, 242),
+            },
+            ThemePreset::Gruvbox => Self {
+                accent: Color::Rgb(254, 128, 25),
+                border: Color::Rgb(146, 131, 116),
+                label: Color::Rgb(250, 189, 47),
+                item: Color::Rgb(184, 187, 38),
+                value: Color::Rgb(235, 219, 178),
+            },
+        }
+    }
+
+    /// Whether everything, logo and badges included, should be drawn
+    /// without colors
+    pub fn is_monochrome(&self) -> bool {
+        *self == Self::preset(ThemePreset::Monochrome)
+    }
+}
+
+/// Whether one-shot output may carry ANSI escapes: not when `NO_COLOR` is
+/// set, nor when stdout is a file or pipe where they'd end up verbatim
+pub fn should_use_color() -> bool {
+    !no_color() && io::stdout().is_terminal()
+}
+
+/// Whether the user asked for no colors through `NO_COLOR`
+fn no_color() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(feature = "wallpaper")]
+mod wallpaper {
+    use std::cmp::Reverse;
+    use std::path::PathBuf;
+    use std::process::{Command, Output};
+
+    use ratatui::style::Color;
+
+    use super::Palette;
+
+    /// Number of color buckets sampled from the image
+    const BUCKETS: usize = 4;
+
+    /// Build a palette from the dominant colors of the current wallpaper
+    pub fn palette(no_exec: bool) -> Option<Palette> {
+        let path = wallpaper_path(no_exec)?;
+        // A thumbnail keeps the quantization cheap for 4K wallpapers
+        let image = image::open(path).ok()?.thumbnail(64, 64).to_rgb8();
+        let pixels = image.pixels().map(|pixel| pixel.0).collect();
+
+        let mut buckets = median_cut(pixels, BUCKETS);
+        buckets.sort_by_key(|&(_, count)| Reverse(count));
+        let mut colors = buckets.into_iter().map(|(rgb, _)| readable(rgb));
+
+        let accent = colors.next()?;
+        Some(Palette {
+            accent,
+            border: accent,
+            label: colors.next()?,
+            item: colors.next()?,
+            value: Color::Reset,
+        })
+    }
+
+    /// Locate the wallpaper through the desktop environment
+    fn wallpaper_path(no_exec: bool) -> Option<PathBuf> {
+        if cfg!(target_os = "linux") {
+            let output = run_command(
+                no_exec,
+                Command::new("gsettings").args([
+                    "get",
+                    "org.gnome.desktop.background",
+                    "picture-uri",
+                ]),
+            )?;
+            // Printed as a quoted URI, e.g. 'file:///usr/share/backgrounds/a%20b.png'
+            let uri = String::from_utf8_lossy(&output.stdout);
+            let path = uri.trim().trim_matches('\'').strip_prefix("file://")?;
+            Some(PathBuf::from(percent_decode(path)))
+        } else if cfg!(target_os = "macos") {
+            let output = run_command(
+                no_exec,
+                Command::new("osascript").args([
+                    "-e",
+                    "tell application \"System Events\" to get picture of current desktop",
+                ]),
+            )?;
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            (!path.is_empty()).then(|| PathBuf::from(path))
+        } else {
+            None
+        }
+    }
+
+    /// Run a desktop settings query unless shell-outs are disabled; a
+    /// failure only means falling back to the regular theme
+    fn run_command(no_exec: bool, command: &mut Command) -> Option<Output> {
+        if no_exec {
+            return None;
+        }
+        command
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    }
+
+    /// Decode `%XX` escapes in a file URI path
+    fn percent_decode(path: &str) -> String {
+        let bytes = path.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let escaped = (bytes[i] == b'%')
+                .then(|| path.get(i + 1..i + 3))
+                .flatten()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match escaped {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                }
+                None => {
+                    decoded.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+        String::from_utf8_lossy(&decoded).into_owned()
+    }
+
+    /// Split the pixels into up to `count` boxes along their widest channel
+    /// and return each box's average color with its pixel count
+    fn median_cut(pixels: Vec<[u8; 3]>, count: usize) -> Vec<([u8; 3], usize)> {
+        let range = |pixels: &[[u8; 3]], channel: usize| {
+            let values = pixels.iter().map(|pixel| pixel[channel]);
+            values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+        };
+
+        let mut boxes = vec![pixels];
+        while boxes.len() < count {
+            let Some(index) = (0..boxes.len())
+                .filter(|&i| boxes[i].len() > 1)
+                .max_by_key(|&i| (0..3).map(|c| range(&boxes[i], c)).max())
+            else {
+                break;
+            };
+
+            let mut pixels = boxes.swap_remove(index);
+            let channel = (0..3).max_by_key(|&c| range(&pixels, c)).unwrap_or(0);
+            pixels.sort_unstable_by_key(|pixel| pixel[channel]);
+            let upper = pixels.split_off(pixels.len() / 2);
+            boxes.push(pixels);
+            boxes.push(upper);
+        }
+
+        boxes
+            .into_iter()
+            .filter(|pixels| !pixels.is_empty())
+            .map(|pixels| {
+                let mut sum = [0usize; 3];
+                for pixel in &pixels {
+                    for c in 0..3 {
+                        sum[c] += pixel[c] as usize;
+                    }
+                }
+                let average = sum.map(|total| (total / pixels.len()) as u8);
+                (average, pixels.len())
+            })
+            .collect()
+    }
+
+    /// Lighten dark colors so text stays legible on a dark terminal
+    fn readable([r, g, b]: [u8; 3]) -> Color {
+        const MIN_LUMA: f32 = 0.55;
+
+        let luma = (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) / 255.0;
+        let mix = if luma < MIN_LUMA {
+            (MIN_LUMA - luma) / (1.0 - luma)
+        } else {
+            0.0
+        };
+        let lighten = |channel: u8| (channel as f32 + (255.0 - channel as f32) * mix).round() as u8;
+        Color::Rgb(lighten(r), lighten(g), lighten(b))
+    }
+}
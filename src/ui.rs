@@ -1,215 +1,694 @@
+use std::borrow::Cow;
+
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout},
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{
+        Block, Borders, Gauge, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Widget,
+        Wrap,
+    },
 };
+#[cfg(feature = "svg")]
+use ratatui::{Terminal, backend::TestBackend};
 
 use crate::app::App;
+use crate::config::{ByteUnits, Config, IconMode, MemoryDisplay};
+use crate::format::{display_width, format_clock, format_usage, pad_to_width, percentage};
 use crate::logo;
+use crate::sections::{self, MAX_LABEL_WIDTH, RenderContext, Section};
+
+/// Below this width the logo moves above the info, or is left out
+const NARROW_WIDTH: u16 = 80;
+
+/// Rows the info panel keeps before a stacked logo is dropped
+const MIN_INFO_HEIGHT: u16 = 12;
+
+/// Sections drawn as gauges instead of text lines when `gauges` is set
+const GAUGE_SECTIONS: &[Section] = &[Section::Memory, Section::Swap, Section::Disk];
+
+/// Height of the info text viewport and how far the text can scroll in it,
+/// as of the last frame
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Viewport {
+    pub height: u16,
+    pub max_scroll: u16,
+}
 
-pub fn draw(f: &mut Frame, app: &App) {
+pub fn draw(f: &mut Frame, app: &App) -> Viewport {
     let size = f.size();
 
-    let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .margin(2)
+    let icons = resolve_icon_mode(app);
+    let mut logo = logo_lines(app, icons);
+    // An empty logo would leave a blank area that looks broken; size the
+    // area for the placeholder drawn instead
+    if logo.is_empty() {
+        logo = logo::get_placeholder_logo();
+    }
+    let logo_size = (
+        logo.iter().map(Line::width).max().unwrap_or(0) as u16,
+        logo.len() as u16,
+    );
+
+    let (logo_area, info_area) = main_layout(size, app.config.rtl, logo_size);
+    if !logo_area.is_empty() {
+        draw_ascii_art(f, logo_area, logo);
+    }
+
+    let cores_height = if app.show_cores {
+        cpu_cores_height(info_area.width, app)
+    } else {
+        0
+    };
+    let interfaces_height = if app.show_interfaces {
+        interfaces_height(app)
+    } else {
+        0
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
         .constraints([
-            Constraint::Percentage(35), // left-side: ASCII art
-            Constraint::Percentage(65), // right-side: system information
+            Constraint::Min(0),
+            Constraint::Length(cores_height),
+            Constraint::Length(interfaces_height),
         ])
+        .split(info_area);
+    let viewport = draw_all_system_info(f, chunks[0], app, icons);
+    if cores_height > 0 {
+        draw_cpu_cores(f, chunks[1], app);
+    }
+    if interfaces_height > 0 {
+        draw_interfaces(f, chunks[2], app, icons);
+    }
+
+    draw_help_simple(f, size, app);
+
+    // The logo, badges and core bars have fixed colors of their own
+    if app.palette.is_monochrome() {
+        let buffer = f.buffer_mut();
+        let area = buffer.area;
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buffer
+                    .get_mut(x, y)
+                    .set_fg(Color::Reset)
+                    .set_bg(Color::Reset);
+            }
+        }
+    }
+    viewport
+}
+
+/// Draw one frame into an off-screen buffer of the given size
+#[cfg(feature = "svg")]
+pub fn render_to_buffer(app: &App, width: u16, height: u16) -> std::io::Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|f| {
+        draw(f, app);
+    })?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Areas of the logo and the info panel; mirrored for right-to-left layouts.
+/// Narrow terminals stack the logo above the info when it fits, and
+/// otherwise leave it out with an empty area
+fn main_layout(size: Rect, rtl: bool, (logo_width, logo_height): (u16, u16)) -> (Rect, Rect) {
+    if size.width < NARROW_WIDTH {
+        let area = size.inner(&Margin::new(1, 1));
+        let fits = logo_width <= area.width
+            && logo_height + MIN_INFO_HEIGHT <= area.height.saturating_sub(1);
+        let logo_height = if fits { logo_height } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(logo_height), Constraint::Min(0)])
+            .split(area);
+        return (chunks[0], chunks[1]);
+    }
+
+    let logo = Constraint::Percentage(35);
+    let info = Constraint::Percentage(65);
+    let constraints = if rtl { [info, logo] } else { [logo, info] };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .margin(2)
+        .constraints(constraints)
         .split(size);
 
-    draw_ascii_art(f, main_chunks[0]);
-    draw_all_system_info(f, main_chunks[1], app);
+    if rtl {
+        (chunks[1], chunks[0])
+    } else {
+        (chunks[0], chunks[1])
+    }
+}
+
+/// Usable width inside the info panel for a terminal of the given size
+pub fn info_panel_width(size: Rect, config: &Config) -> u16 {
+    // The logo only changes the height of the panel, never its width
+    let width = main_layout(size, config.rtl, (0, 0)).1.width;
+    if config.show_border {
+        width.saturating_sub(2)
+    } else {
+        width
+    }
+}
 
-    draw_help_simple(f, size);
+/// The custom logo if one was given, otherwise the logo of the detected
+/// distribution or the default one matching the icon set
+pub fn logo_lines(app: &App, icons: IconMode) -> Vec<Line<'static>> {
+    if let Some(custom) = &app.custom_logo {
+        return custom.clone();
+    }
+    // Non-Linux systems have no os-release ID
+    let distro_id = app
+        .system_info
+        .distro_id
+        .as_deref()
+        .unwrap_or(std::env::consts::OS);
+    match icons {
+        IconMode::Emoji => logo::get_logo_for(distro_id),
+        IconMode::Nerd => logo::distro_logo(distro_id).unwrap_or_else(logo::get_logo_nerd),
+        IconMode::Ascii => logo::distro_logo(distro_id).unwrap_or_else(logo::get_logo_ascii),
+    }
 }
 
-fn draw_ascii_art(f: &mut Frame, area: ratatui::layout::Rect) {
-    let ascii_art = logo::get_logo();
+/// Draw the logo; lines wider than the area are cut off
+fn draw_ascii_art(f: &mut Frame, area: ratatui::layout::Rect, ascii_art: Vec<Line<'static>>) {
     let paragraph = Paragraph::new(ascii_art).alignment(Alignment::Left);
     f.render_widget(paragraph, area);
 }
 
-fn draw_all_system_info(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    let info = &app.system_info;
+fn draw_all_system_info(
+    f: &mut Frame,
+    area: ratatui::layout::Rect,
+    app: &App,
+    icons: IconMode,
+) -> Viewport {
+    let width = if app.config.show_border {
+        area.width.saturating_sub(2)
+    } else {
+        area.width
+    };
+    let title = title_spans(app, icons);
+    let skip = if app.config.gauges {
+        GAUGE_SECTIONS
+    } else {
+        &[]
+    };
+    let mut text = section_lines(app, icons, width, skip);
 
-    let mut text = vec![
-        Line::from(vec![
-            Span::styled(
-                "  OS: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(format!(
-                "{} {}, {}",
-                info.os_name, info.os_version, info.os_arch
-            )),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Kernel: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(&info.kernel_version),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Host: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(&info.hostname),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  User: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(&info.username),
-        ]),
-        Line::from(vec![
-            Span::styled(
-                "  Uptime: ",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(&info.uptime),
-        ]),
-        Line::from(""),
-    ];
-
-    for (i, cpu) in info.cpus.iter().enumerate() {
-        if i == 0 {
-            text.push(Line::from(Span::styled(
-                " 🔥 CPUs",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )));
-        }
+    let inner = if app.config.show_border {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(app.palette.border))
+            .title(Line::from(title))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        inner
+    } else {
+        text.insert(0, Line::from(title).alignment(Alignment::Center));
+        area
+    };
+
+    let gauges = if app.config.gauges {
+        usage_gauges(app, icons)
+    } else {
+        Vec::new()
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(gauges.len() as u16)])
+        .split(inner);
+
+    let alignment = if app.config.rtl {
+        Alignment::Right
+    } else {
+        Alignment::Left
+    };
+    let line_widths: Vec<usize> = text.iter().map(Line::width).collect();
+    let paragraph = Paragraph::new(text)
+        .alignment(alignment)
+        .wrap(Wrap { trim: false });
 
-        let cpu_name = format!(
-            "{} ({} cores) @ {:.2}GHz",
-            cpu.model
-                .split_whitespace()
-                .take(4)
-                .collect::<Vec<_>>()
-                .join(" "),
-            cpu.cores,
-            cpu.frequency as f64 / 1000.0
+    let mut text_area = chunks[0];
+    let mut content_height = wrapped_height(&paragraph, &line_widths, text_area.width);
+    // Without a border the scrollbar needs a column of its own
+    if content_height > text_area.height && !app.config.show_border {
+        text_area.width = text_area.width.saturating_sub(1);
+        content_height = wrapped_height(&paragraph, &line_widths, text_area.width);
+    }
+    let viewport = Viewport {
+        height: text_area.height,
+        max_scroll: content_height.saturating_sub(text_area.height),
+    };
+    let scroll = app.scroll.min(viewport.max_scroll);
+    f.render_widget(paragraph.scroll((scroll, 0)), text_area);
+
+    if viewport.max_scroll > 0 {
+        // Drawn over the right border, or beside the text without one
+        let track = if app.config.show_border {
+            Rect {
+                x: area.right().saturating_sub(1),
+                y: chunks[0].y,
+                width: 1,
+                height: chunks[0].height,
+            }
+        } else {
+            chunks[0]
+        };
+        // Positions run up to `max_scroll`, so scale the viewport to keep the
+        // thumb as long as the visible share of the text
+        let max_scroll = viewport.max_scroll as usize;
+        let mut state = ScrollbarState::new(max_scroll)
+            .position(scroll as usize)
+            .viewport_content_length(
+                (viewport.height as usize * max_scroll / content_height as usize).max(1),
+            );
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(app.palette.border)),
+            track,
+            &mut state,
         );
+    }
 
-        text.push(Line::from(vec![
-            Span::styled(
-                format!("  - CPU {}: ", i + 1),
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(cpu_name),
-        ]));
-    }
-
-    for (i, gpu) in info.gpus.iter().enumerate() {
-        if i == 0 {
-            text.push(Line::from(Span::styled(
-                " 🎮 GPUs",
-                Style::default()
-                    .fg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD),
-            )));
-        }
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Length(1); gauges.len()])
+        .split(chunks[1]);
+    // Bars start in one column, like the aligned text values
+    let label_width = gauges
+        .iter()
+        .map(|(label, _, _)| display_width(label) + 2)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_LABEL_WIDTH);
+    for ((label, used, total), row) in gauges.into_iter().zip(rows.iter()) {
+        let label = pad_to_width(&format!("{label}: "), label_width);
+        draw_usage_bar(f, *row, app, &label, used, total);
+    }
+    viewport
+}
 
-        text.push(Line::from(vec![
-            Span::styled(
-                format!("  - GPU {}: ", i + 1),
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(gpu.name.clone()),
-        ]));
+/// Rows the paragraph takes once wrapped to `width`. Ratatui doesn't expose
+/// its word wrapping, so render into a scratch buffer tall enough for any
+/// wrapping (greedy wrapping fills at least half of every two rows) and
+/// find the last row with text.
+fn wrapped_height(paragraph: &Paragraph, line_widths: &[usize], width: u16) -> u16 {
+    if width == 0 {
+        return 0;
     }
+    let bound: usize = line_widths
+        .iter()
+        .map(|line_width| 2 * line_width.div_ceil(width as usize) + 1)
+        .sum();
+    let area = Rect::new(0, 0, width, bound.min(u16::MAX as usize) as u16);
+    let mut buffer = Buffer::empty(area);
+    paragraph.clone().render(area, &mut buffer);
+    (0..area.height)
+        .rev()
+        .find(|&y| (0..width).any(|x| !buffer.get(x, y).symbol().trim().is_empty()))
+        .map_or(0, |y| y + 1)
+}
 
-    text.push(Line::from(""));
+/// Label, used and total bytes of each visible memory, swap and disk gauge
+fn usage_gauges(app: &App, icons: IconMode) -> Vec<(String, u64, u64)> {
+    let info = &app.system_info;
+    let ctx = RenderContext {
+        info,
+        config: &app.config,
+        icons,
+        cpu_history: None,
+        palette: app.palette,
+        width: 0,
+    };
+    let mut gauges = Vec::new();
+    for section in sections::layout(&app.config) {
+        let label = sections::spec(section).label(&ctx);
+        match section {
+            Section::Memory => gauges.push((label, info.memory_used, info.memory_total)),
+            Section::Swap => gauges.push((label, info.swap_used, info.swap_total)),
+            Section::Disk => gauges.extend(info.disks.iter().map(|disk| {
+                (
+                    format!("{label} {}", disk.mount_point),
+                    disk.used,
+                    disk.total,
+                )
+            })),
+            _ => {}
+        }
+    }
+    gauges
+}
 
-    text.push(Line::from(vec![
-        Span::styled(
-            " 🌐 Local IP: ",
+/// One row with a label and a usage gauge colored from green to red by
+/// the percentage used; a zero total (e.g. no swap) draws an empty,
+/// disabled bar
+fn draw_usage_bar(f: &mut Frame, area: Rect, app: &App, label: &str, used: u64, total: u64) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(display_width(label) as u16),
+            Constraint::Min(0),
+        ])
+        .split(area);
+    f.render_widget(
+        Paragraph::new(Span::styled(
+            label.to_string(),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.palette.label)
                 .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(&info.local_ip),
-    ]));
+        )),
+        chunks[0],
+    );
 
-    text.push(Line::from(vec![
-        Span::styled(
-            " 🐚 Shell: ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(&info.shell),
-    ]));
+    let gauge = if total == 0 {
+        Gauge::default()
+            .gauge_style(Style::default().fg(Color::DarkGray))
+            .ratio(0.0)
+            .label("disabled")
+    } else {
+        let percent = percentage(used, total);
+        Gauge::default()
+            .gauge_style(Style::default().fg(usage_color(percent as f32)))
+            .ratio((percent / 100.0).clamp(0.0, 1.0))
+            .label(format_usage(
+                used,
+                total,
+                app.config.byte_units == ByteUnits::Binary,
+                MemoryDisplay::Full,
+                app.config.percent_precision,
+            ))
+    };
+    f.render_widget(gauge, chunks[1]);
+}
+
+/// Green below 50%, yellow below 80%, red above
+fn usage_color(percent: f32) -> Color {
+    if percent >= 80.0 {
+        Color::Red
+    } else if percent >= 50.0 {
+        Color::Yellow
+    } else {
+        Color::Green
+    }
+}
 
-    text.push(Line::from(vec![
-        Span::styled(
-            " 📟 Terminal: ",
+/// Panel title, with a badge in demo mode
+pub fn title_spans(app: &App, icons: IconMode) -> Vec<Span<'static>> {
+    let title_style = Style::default()
+        .fg(app.palette.accent)
+        .add_modifier(Modifier::BOLD);
+    let mut title = vec![Span::styled(
+        format!("{} ", sections::title_label(&app.config, icons)),
+        title_style,
+    )];
+    if app.config.demo_seed.is_some() {
+        // Make it obvious the figures are not real
+        title.push(Span::styled(
+            " DEMO ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(Color::Black)
+                .bg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(&info.terminal),
-    ]));
+        ));
+        title.push(Span::raw(" "));
+    }
+    title
+}
+
+/// Lines of every visible section with aligned values, a blank line
+/// between groups and change markers while they last
+pub fn info_lines(app: &App, icons: IconMode, width: u16) -> Vec<Line<'static>> {
+    section_lines(app, icons, width, &[])
+}
 
-    let memory_percent = if info.memory_total > 0 {
-        (info.memory_used as f64 / info.memory_total as f64 * 100.0) as u16
+/// Like `info_lines`, leaving out the `skip` sections
+fn section_lines(app: &App, icons: IconMode, width: u16, skip: &[Section]) -> Vec<Line<'static>> {
+    let info = if app.hide_ip {
+        let mut info = app.system_info.clone();
+        info.mask_addresses();
+        Cow::Owned(info)
     } else {
-        0
+        Cow::Borrowed(&app.system_info)
     };
-    text.push(Line::from(vec![
-        Span::styled(
-            " 💾 Memory: ",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(format!(
-            "{}MiB / {}MiB ({}%)",
-            info.memory_used / (1024 * 1024),
-            info.memory_total / (1024 * 1024),
-            memory_percent
-        )),
-    ]));
 
-    let paragraph = Paragraph::new(text)
-        .block(
+    let ctx = RenderContext {
+        info: &info,
+        config: &app.config,
+        icons,
+        cpu_history: app.config.watch.then_some(&app.cpu_history),
+        palette: app.palette,
+        width,
+    };
+
+    let show_changes = app.highlight_remaining().is_some();
+    let mut text = Vec::new();
+    let mut last_group = None;
+    for section in sections::layout(&app.config) {
+        let spec = sections::spec(section);
+        if !spec.is_available() || skip.contains(&section) {
+            continue;
+        }
+
+        let mut lines = (spec.render)(&ctx, spec);
+        if lines.is_empty() {
+            continue;
+        }
+        if show_changes && app.changed.iter().any(|key| key == spec.key) {
+            let marker = if icons == IconMode::Ascii {
+                " *"
+            } else {
+                " ●"
+            };
+            lines[0]
+                .spans
+                .push(Span::styled(marker, Style::default().fg(app.palette.item)));
+        }
+        if last_group.is_some_and(|group| group != spec.group) {
+            text.push(Line::from(""));
+        }
+        last_group = Some(spec.group);
+        text.extend(lines);
+    }
+
+    align_values(&mut text);
+    text
+}
+
+/// Cells in the bar of a per-core gauge
+const CORE_BAR_WIDTH: usize = 8;
+
+/// Width of one per-core gauge cell for the given number of cores,
+/// including the gap before the next column
+fn core_cell_width(cores: usize) -> usize {
+    let index_width = cores.to_string().len();
+    // "<index> <bar> 100%" plus a gap
+    index_width + 1 + CORE_BAR_WIDTH + 1 + 4 + 2
+}
+
+/// Rows of the per-core grid when packed into as many columns as fit
+fn core_rows(width: u16, app: &App) -> usize {
+    let cores = app.system_info.core_usage.len();
+    let inner = if app.config.show_border {
+        width.saturating_sub(2)
+    } else {
+        width
+    } as usize;
+    let columns = (inner / core_cell_width(cores)).clamp(1, cores.max(1));
+    cores.div_ceil(columns)
+}
+
+/// Height of the per-core panel, or 0 when there is nothing to show
+fn cpu_cores_height(width: u16, app: &App) -> u16 {
+    if app.system_info.core_usage.is_empty() {
+        return 0;
+    }
+    let rows = core_rows(width, app);
+    let border = if app.config.show_border { 2 } else { 0 };
+    rows as u16 + border
+}
+
+/// Per-core usage as small bars colored by load, packed into as many
+/// columns as the panel width allows
+fn draw_cpu_cores(f: &mut Frame, area: Rect, app: &App) {
+    let usage = &app.system_info.core_usage;
+    let ascii = resolve_icon_mode(app) == IconMode::Ascii;
+    let (full, empty) = if ascii { ('#', '.') } else { ('█', '░') };
+    let rows = core_rows(area.width, app);
+    let index_width = usage.len().to_string().len();
+
+    let mut text = vec![Line::default(); rows];
+    // Column-major, so core numbers read top to bottom like `htop`
+    for (core, &load) in usage.iter().enumerate() {
+        let color = usage_color(load);
+        let filled = ((load.clamp(0.0, 100.0) / 100.0) * CORE_BAR_WIDTH as f32).round() as usize;
+        let line = &mut text[core % rows];
+        line.spans.push(Span::styled(
+            format!("{core:>index_width$} "),
+            Style::default().fg(app.palette.label),
+        ));
+        line.spans.push(Span::styled(
+            full.to_string().repeat(filled),
+            Style::default().fg(color),
+        ));
+        line.spans.push(Span::styled(
+            empty.to_string().repeat(CORE_BAR_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ));
+        line.spans.push(Span::raw(format!(" {:>3.0}%  ", load)));
+    }
+
+    let paragraph = Paragraph::new(text);
+    let paragraph = if app.config.show_border {
+        paragraph.block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" 🖥️  Environments ")
-                .title_alignment(Alignment::Center)
-                .title_style(
+                .border_style(Style::default().fg(app.palette.border))
+                .title(Span::styled(
+                    " Cores ",
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(app.palette.accent)
                         .add_modifier(Modifier::BOLD),
+                ))
+                .title_alignment(Alignment::Center),
+        )
+    } else {
+        paragraph
+    };
+    f.render_widget(paragraph, area);
+}
+
+/// Height of the interfaces panel, or 0 when there is nothing to show
+fn interfaces_height(app: &App) -> u16 {
+    let interfaces = app.system_info.interfaces.len() as u16;
+    let border = if app.config.show_border { 2 } else { 0 };
+    if interfaces == 0 {
+        0
+    } else {
+        interfaces + border
+    }
+}
+
+/// One line per network interface with its MAC and addresses, the one
+/// carrying the default route marked
+fn draw_interfaces(f: &mut Frame, area: Rect, app: &App, icons: IconMode) {
+    let info = if app.hide_ip {
+        let mut info = app.system_info.clone();
+        info.mask_addresses();
+        Cow::Owned(info)
+    } else {
+        Cow::Borrowed(&app.system_info)
+    };
+    let name_width = info
+        .interfaces
+        .iter()
+        .map(|interface| display_width(&interface.name))
+        .max()
+        .unwrap_or(0);
+    let marker = if icons == IconMode::Ascii { "*" } else { "★" };
+
+    let text: Vec<Line> = info
+        .interfaces
+        .iter()
+        .map(|interface| {
+            let mut spans = vec![
+                Span::styled(
+                    pad_to_width(&interface.name, name_width + 1),
+                    Style::default()
+                        .fg(app.palette.label)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    if interface.default_route {
+                        format!("{marker} default  ")
+                    } else {
+                        " ".repeat(display_width(marker) + 10)
+                    },
+                    Style::default().fg(app.palette.accent),
                 ),
+            ];
+            if let Some(mac) = &interface.mac {
+                spans.push(Span::styled(
+                    format!("{mac}  "),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let addresses: Vec<&str> = interface
+                .ipv4
+                .iter()
+                .chain(&interface.ipv6)
+                .map(String::as_str)
+                .collect();
+            spans.push(Span::styled(
+                addresses.join(", "),
+                Style::default().fg(app.palette.value),
+            ));
+            Line::from(spans)
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(text);
+    let paragraph = if app.config.show_border {
+        paragraph.block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.palette.border))
+                .title(Span::styled(
+                    " Interfaces ",
+                    Style::default()
+                        .fg(app.palette.accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .title_alignment(Alignment::Center),
         )
-        .wrap(Wrap { trim: false });
+    } else {
+        paragraph
+    };
     f.render_widget(paragraph, area);
 }
 
-fn draw_help_simple(f: &mut Frame, size: ratatui::layout::Rect) {
+/// Pad the label of every "label: value" line so the values line up,
+/// measuring in terminal cells so emoji-prefixed labels don't drift
+fn align_values(lines: &mut [Line]) {
+    // Grid rows start with a bare indent and keep their own column layout
+    let is_aligned = |line: &Line| {
+        line.spans.len() > 1
+            && line.spans[0].content.ends_with(": ")
+            && display_width(&line.spans[0].content) <= MAX_LABEL_WIDTH
+    };
+    let width = lines
+        .iter()
+        .filter(|line| is_aligned(line))
+        .map(|line| display_width(&line.spans[0].content))
+        .max()
+        .unwrap_or(0);
+
+    for line in lines.iter_mut().filter(|line| is_aligned(line)) {
+        let label = &mut line.spans[0];
+        label.content = pad_to_width(&label.content, width).into();
+    }
+}
+
+/// `--icons` wins, then ASCII mode, then Nerd Font glyphs when a Nerd Font
+/// is installed
+pub fn resolve_icon_mode(app: &App) -> IconMode {
+    if let Some(icons) = app.config.icon_mode {
+        icons
+    } else if app.config.ascii_mode() {
+        IconMode::Ascii
+    } else if app.system_info.nerd_font {
+        IconMode::Nerd
+    } else {
+        IconMode::Emoji
+    }
+}
+
+fn draw_help_simple(f: &mut Frame, size: ratatui::layout::Rect, app: &App) {
     let help_area = ratatui::layout::Rect {
         x: 0,
         y: size.height.saturating_sub(1),
@@ -217,8 +696,71 @@ fn draw_help_simple(f: &mut Frame, size: ratatui::layout::Rect) {
         height: 1,
     };
 
-    let help_text = Paragraph::new("Press 'q' or 'Esc' to quit")
+    let mut help = String::from(if size.width < NARROW_WIDTH {
+        "q quit · i IP · c cores · n net · y copy"
+    } else {
+        "Press 'q' or 'Esc' to quit, 'i' to toggle IP, 'c' for cores, 'n' for interfaces, \
+         'y' to copy"
+    });
+    if let Some(status) = app.status() {
+        help = status.to_string();
+    } else if app.config.refresh > 0 {
+        help.push_str(&format!(
+            " · updated {}",
+            format_clock(app.system_info.local_time)
+        ));
+    }
+    let help_text = Paragraph::new(help)
         .style(Style::default().fg(Color::Gray))
         .alignment(Alignment::Center);
     f.render_widget(help_text, help_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    fn screen(app: &App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|f| {
+                draw(f, app);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let mut text = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                text.push_str(buffer.get(x, y).symbol());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    #[test]
+    fn empty_logo_shows_the_placeholder_in_a_narrow_terminal() {
+        let path =
+            std::env::temp_dir().join(format!("rsysfetch-empty-logo-{}", std::process::id()));
+        fs::write(&path, "").unwrap();
+        let app = App::new(Config {
+            demo_seed: Some(1),
+            logo: Some(path.clone()),
+            ..Config::default()
+        });
+        fs::remove_file(&path).unwrap();
+        let app = app.unwrap();
+
+        for (width, height) in [(60, 40), (120, 40)] {
+            let screen = screen(&app, width, height);
+            assert!(
+                screen.contains("|  >_   |"),
+                "no placeholder at {width}x{height}:\n{screen}"
+            );
+        }
+    }
+}